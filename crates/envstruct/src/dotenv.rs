@@ -0,0 +1,214 @@
+use crate::*;
+use std::{collections::BTreeMap, path::Path};
+
+/// A source of variables loaded from a `.env` file.
+///
+/// The file consists of `KEY=VALUE` lines and supports:
+///
+/// * comments starting with `#`, both on their own line and after unquoted values,
+/// * an optional `export` prefix,
+/// * single-quoted values, taken literally,
+/// * double-quoted values with the escape sequences `\n`, `\r`, `\t`, `\\`, `\"` and `\$`,
+/// * quoted values spanning multiple lines.
+///
+/// Use [`EnvSource::fallback`] to layer the file under the process environment:
+///
+/// ```no_run
+/// use envstruct::prelude::*;
+///
+/// #[derive(EnvStruct)]
+/// pub struct Config {
+///     pub port: u16,
+/// }
+///
+/// let source = ProcessEnv.fallback(DotEnv::load(".env")?);
+/// let config = Config::from_source(&source, "MY_APP")?;
+/// # Ok::<(), EnvStructError>(())
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct DotEnv {
+    path: String,
+    vars: BTreeMap<String, DotEnvVar>,
+}
+
+/// A variable read from a `.env` file with the line number it is defined on.
+#[derive(Debug, Clone)]
+struct DotEnvVar {
+    value: String,
+    line: usize,
+}
+
+impl DotEnv {
+    /// Loads the variables from a `.env` file.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `EnvStructError` if the file cannot be read or has an invalid syntax.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, EnvStructError> {
+        let path = path.as_ref();
+        let content = std::fs::read_to_string(path).map_err(|e| EnvStructError::ReadFileError {
+            path: path.display().to_string(),
+            source: e,
+        })?;
+        Self::parse(path, &content)
+    }
+
+    /// Parses the variables from the content of a `.env` file.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the file, used to describe the origin of the variables.
+    /// * `content` - The content of the file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `EnvStructError` if the content has an invalid syntax.
+    pub fn parse(path: impl AsRef<Path>, content: &str) -> Result<Self, EnvStructError> {
+        let path = path.as_ref().display().to_string();
+        let syntax_error = |line: usize, message: &str| EnvStructError::DotEnvSyntaxError {
+            path: path.clone(),
+            line,
+            message: message.to_string(),
+        };
+
+        let lines: Vec<&str> = content.lines().collect();
+        let mut vars = BTreeMap::new();
+        let mut index = 0;
+        while index < lines.len() {
+            let line = index + 1;
+            let entry = lines[index].trim();
+            index += 1;
+
+            if entry.is_empty() || entry.starts_with('#') {
+                continue;
+            }
+
+            let entry = entry
+                .strip_prefix("export")
+                .filter(|rest| rest.starts_with(char::is_whitespace))
+                .unwrap_or(entry);
+
+            let Some((key, value)) = entry.split_once('=') else {
+                return Err(syntax_error(line, "expected `KEY=VALUE`"));
+            };
+
+            let key = key.trim();
+            if key.is_empty()
+                || !key
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '.')
+            {
+                return Err(syntax_error(line, &format!("invalid key `{key}`")));
+            }
+
+            let value = value.trim_start();
+            let value = match value.chars().next() {
+                Some(quote @ ('"' | '\'')) => {
+                    let mut buf = value[1..].to_string();
+                    let end = loop {
+                        if let Some(end) = find_closing_quote(&buf, quote) {
+                            break end;
+                        }
+                        let Some(next) = lines.get(index) else {
+                            return Err(syntax_error(line, "unterminated quoted value"));
+                        };
+                        buf.push('\n');
+                        buf.push_str(next);
+                        index += 1;
+                    };
+
+                    let rest = buf[end + 1..].trim();
+                    if !rest.is_empty() && !rest.starts_with('#') {
+                        return Err(syntax_error(
+                            line,
+                            &format!("unexpected `{rest}` after quoted value"),
+                        ));
+                    }
+
+                    match quote {
+                        '"' => unescape(&buf[..end]),
+                        _ => buf[..end].to_string(),
+                    }
+                }
+                _ => strip_comment(value).trim_end().to_string(),
+            };
+
+            vars.insert(key.to_string(), DotEnvVar { value, line });
+        }
+
+        Ok(Self { path, vars })
+    }
+}
+
+impl EnvSource for DotEnv {
+    fn var(&self, name: &str) -> Result<Option<String>, EnvStructError> {
+        Ok(self.vars.get(name).map(|var| var.value.clone()))
+    }
+
+    fn var_names(&self, prefix: &str) -> Vec<String> {
+        self.vars
+            .keys()
+            .filter(|name| name.starts_with(prefix))
+            .cloned()
+            .collect()
+    }
+
+    fn origin(&self, name: &str) -> Option<String> {
+        self.vars
+            .get(name)
+            .map(|var| format!("{}:{}", self.path, var.line))
+    }
+}
+
+/// Finds the byte index of the quote closing a quoted value, skipping escaped double quotes.
+fn find_closing_quote(value: &str, quote: char) -> Option<usize> {
+    let mut escaped = false;
+    for (index, c) in value.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if quote == '"' => escaped = true,
+            c if c == quote => return Some(index),
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Replaces the escape sequences of a double-quoted value.
+fn unescape(value: &str) -> String {
+    let mut result = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            result.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => result.push('\n'),
+            Some('r') => result.push('\r'),
+            Some('t') => result.push('\t'),
+            Some(c @ ('\\' | '"' | '$')) => result.push(c),
+            Some(c) => {
+                result.push('\\');
+                result.push(c);
+            }
+            None => result.push('\\'),
+        }
+    }
+    result
+}
+
+/// Strips a trailing comment from an unquoted value, a `#` preceded by whitespace.
+fn strip_comment(value: &str) -> &str {
+    value
+        .char_indices()
+        .find(|&(index, c)| {
+            c == '#' && (index == 0 || value[..index].ends_with(char::is_whitespace))
+        })
+        .map(|(index, _)| &value[..index])
+        .unwrap_or(value)
+}
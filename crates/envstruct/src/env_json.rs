@@ -19,6 +19,11 @@ impl<T: for<'a> serde::de::Deserialize<'a>> EnvParsePrimitive for EnvJson<T> {
     fn parse(val: &str) -> Result<Self, BoxError> {
         Ok(EnvJson(serde_json::from_str(val)?))
     }
+
+    /// Returns the hint shown in errors
+    fn hint() -> Option<String> {
+        Some("JSON".to_string())
+    }
 }
 
 /// Implementation of AsRef to get a reference to the inner value
@@ -1,96 +1,218 @@
 use crate::*;
-use std::{collections::HashMap, str::FromStr};
+use std::{
+    borrow::Borrow,
+    collections::{BTreeMap, HashMap},
+    str::FromStr,
+};
+
+macro_rules! implement_env_map {
+    ($name:ident, $map:ident, $($bound:tt)+) => {
+        impl<K, V> AsRef<$map<K, V>> for $name<K, V> {
+            #[doc = concat!("Returns a reference to the underlying `", stringify!($map), "`.")]
+            fn as_ref(&self) -> &$map<K, V> {
+                &self.0
+            }
+        }
+
+        impl<K, V> std::ops::Deref for $name<K, V> {
+            type Target = $map<K, V>;
+            #[doc = concat!("Dereferences to the underlying `", stringify!($map), "`.")]
+            fn deref(&self) -> &Self::Target {
+                &self.0
+            }
+        }
+
+        impl<K, V> std::ops::DerefMut for $name<K, V> {
+            #[doc = concat!("Dereferences to the underlying mutable `", stringify!($map), "`.")]
+            fn deref_mut(&mut self) -> &mut Self::Target {
+                &mut self.0
+            }
+        }
+
+        impl<K, V> EnvParseNested for $name<K, V>
+        where
+            K: FromStr + $($bound)+,
+            V: EnvParsePrimitive,
+        {
+            #[doc = concat!("Parses the variables from the given source into an `", stringify!($name), "`.")]
+            ///
+            /// # Arguments
+            ///
+            /// * `source` - The source to look the variables up in.
+            /// * `var_name` - The prefix of the environment variables to parse.
+            /// * `default` - An optional default value.
+            /// * `naming` - The naming policy, its separator follows the prefix.
+            ///
+            /// # Errors
+            ///
+            /// Returns an `EnvStructError` if parsing fails or keys are duplicated.
+            fn parse_from_source_named(
+                source: &dyn EnvSource,
+                var_name: impl AsRef<str>,
+                default: Option<&str>,
+                naming: &EnvNaming,
+            ) -> Result<Self, EnvStructError>
+            where
+                Self: Sized,
+            {
+                let entries = parse_map_entries::<K, V>(source, var_name.as_ref(), default, naming)?;
+                let mut map = $map::new();
+                for (name, key, value) in entries {
+                    map.entry(key)
+                        .or_insert_with(|| (Vec::new(), value))
+                        .0
+                        .push(name);
+                }
+                check_duplicate_keys(map.values().map(|(names, _)| names))?;
+                Ok(Self(
+                    map.into_iter()
+                        .map(|(key, (_, value))| (key, value))
+                        .collect(),
+                ))
+            }
+
+            #[doc = concat!("Gets the environment entries for the `", stringify!($name), "`.")]
+            ///
+            /// # Arguments
+            ///
+            /// * `prefix` - The prefix for the environment entries.
+            /// * `default` - An optional default value.
+            /// * `naming` - The naming policy, its separator follows the prefix.
+            ///
+            /// # Returns
+            ///
+            /// A vector of `EnvEntry` objects.
+            ///
+            /// # Errors
+            ///
+            /// Returns an `EnvStructError` if retrieval fails.
+            fn get_env_entries_named(
+                prefix: impl AsRef<str>,
+                default: Option<&str>,
+                naming: &EnvNaming,
+            ) -> Result<Vec<EnvEntry>, EnvStructError> {
+                Ok(vec![map_entry::<Self>(prefix.as_ref(), default, naming)])
+            }
+        }
+    };
+}
 
 /// A wrapper around `HashMap` to provide environment variable parsing functionality.
+///
+/// Every variable under the prefix and the separator is an entry, e.g. `APP_LABELS_TEAM` is the
+/// key `TEAM` of the map `APP_LABELS`, while `APP_LABELSX_TEAM` is not part of it. The keys are
+/// parsed with `K::from_str`, e.g. `EnvMap<LowercaseKey, String>` lowercases them. Variables
+/// whose keys are equal after parsing are reported as `EnvStructError::DuplicateKey`.
 #[derive(Debug, Clone)]
 pub struct EnvMap<K, V>(pub HashMap<K, V>);
 
-impl<K, V> AsRef<HashMap<K, V>> for EnvMap<K, V> {
-    /// Returns a reference to the underlying `HashMap`.
-    fn as_ref(&self) -> &HashMap<K, V> {
-        &self.0
+implement_env_map!(EnvMap, HashMap, std::hash::Hash + std::cmp::Eq);
+
+/// A wrapper around `BTreeMap` to provide environment variable parsing functionality, the
+/// ordered counterpart of [`EnvMap`].
+#[derive(Debug, Clone)]
+pub struct EnvBTreeMap<K, V>(pub BTreeMap<K, V>);
+
+implement_env_map!(EnvBTreeMap, BTreeMap, std::cmp::Ord);
+
+/// A key of an `EnvMap` or `EnvBTreeMap` lowercased when parsed, e.g. `team` for
+/// `APP_LABELS_TEAM`.
+///
+/// It borrows as `str`, so the map can be queried with `map.get("team")`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct LowercaseKey(pub String);
+
+impl FromStr for LowercaseKey {
+    type Err = std::convert::Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Self(s.to_lowercase()))
     }
 }
 
-impl<K, V> std::ops::Deref for EnvMap<K, V> {
-    type Target = HashMap<K, V>;
-    /// Dereferences to the underlying `HashMap`.
+impl std::ops::Deref for LowercaseKey {
+    type Target = str;
+    /// Dereferences to the lowercased key.
     fn deref(&self) -> &Self::Target {
         &self.0
     }
 }
 
-impl<K, V> std::ops::DerefMut for EnvMap<K, V> {
-    /// Dereferences to the underlying mutable `HashMap`.
-    fn deref_mut(&mut self) -> &mut Self::Target {
-        &mut self.0
+impl Borrow<str> for LowercaseKey {
+    fn borrow(&self) -> &str {
+        &self.0
+    }
+}
+
+impl std::fmt::Display for LowercaseKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
     }
 }
 
-impl<K, V> EnvParseNested for EnvMap<K, V>
+/// Parses the variables under `var_name` and the separator into their names, keys and values,
+/// in the order of the names.
+fn parse_map_entries<K, V>(
+    source: &dyn EnvSource,
+    var_name: &str,
+    default: Option<&str>,
+    naming: &EnvNaming,
+) -> Result<Vec<(String, K, V)>, EnvStructError>
 where
-    K: FromStr + std::hash::Hash + std::cmp::Eq,
+    K: FromStr,
     V: EnvParsePrimitive,
 {
-    /// Parses the environment variables into an `EnvMap`.
-    ///
-    /// # Arguments
-    ///
-    /// * `var_name` - The prefix of the environment variables to parse.
-    /// * `default` - An optional default value.
-    ///
-    /// # Errors
-    ///
-    /// Returns an `EnvStructError` if parsing fails.
-    fn parse_from_env_var(
-        var_name: impl AsRef<str>,
-        default: Option<&str>,
-    ) -> Result<Self, EnvStructError>
-    where
-        Self: Sized,
-    {
-        let var_name = var_name.as_ref();
-        let map = std::env::vars()
-            .filter_map(|(k, _)| {
-                let key = k
-                    .strip_prefix(var_name)
-                    .map(|key| key.trim_start_matches('_'))?
-                    .to_string();
-                Some((k, key))
-            })
-            .map(|(k, key)| {
-                Ok((
-                    K::from_str(&key)
-                        .map_err(|_| EnvStructError::InvalidKeyFormat(k.to_string()))?,
-                    V::parse_from_env_var(k, default)?,
-                ))
-            })
-            .collect::<Result<HashMap<_, _>, _>>()?;
-        Ok(Self(map))
+    let start = format!("{var_name}{}", naming.separator);
+    let mut names = source.var_names(&start);
+    names.sort();
+
+    let mut errors = EnvStructErrors::new();
+    let mut entries = Vec::new();
+    for name in names {
+        let Some(key) = name.strip_prefix(&start).filter(|key| !key.is_empty()) else {
+            continue;
+        };
+        let key = errors.collect(
+            K::from_str(key).map_err(|_| EnvStructError::InvalidKeyFormat(name.to_string())),
+        );
+        let value = errors.collect(V::parse_from_source(source, &name, default));
+        if let (Some(key), Some(value)) = (key, value) {
+            entries.push((name, key, value));
+        }
+    }
+    if !errors.is_empty() {
+        return Err(errors.into());
     }
+    Ok(entries)
+}
 
-    /// Gets the environment entries for the `EnvMap`.
-    ///
-    /// # Arguments
-    ///
-    /// * `prefix` - The prefix for the environment entries.
-    /// * `default` - An optional default value.
-    ///
-    /// # Returns
-    ///
-    /// A vector of `EnvEntry` objects.
-    ///
-    /// # Errors
-    ///
-    /// Returns an `EnvStructError` if retrieval fails.
-    fn get_env_entries(
-        prefix: impl AsRef<str>,
-        default: Option<&str>,
-    ) -> Result<Vec<EnvEntry>, EnvStructError> {
-        Ok(vec![EnvEntry {
-            name: format!("{}_*", prefix.as_ref()),
-            typ: std::any::type_name::<Self>().to_string(),
-            default: default.map(|v| v.to_string()),
-        }])
+/// Reports the variables whose keys are equal after parsing, instead of picking one of them.
+///
+/// # Arguments
+///
+/// * `names` - The sorted names of the variables of each key.
+fn check_duplicate_keys<'a>(
+    names: impl Iterator<Item = &'a Vec<String>>,
+) -> Result<(), EnvStructError> {
+    let mut duplicates: Vec<_> = names.filter(|names| names.len() > 1).collect();
+    duplicates.sort();
+    let mut errors = EnvStructErrors::new();
+    for var_names in duplicates {
+        errors.push(EnvStructError::DuplicateKey {
+            var_names: var_names.clone(),
+        });
     }
+    match errors.is_empty() {
+        true => Ok(()),
+        false => Err(errors.into()),
+    }
+}
+
+/// Describes the variables of a map, e.g. `APP_LABELS_*`.
+fn map_entry<T>(prefix: &str, default: Option<&str>, naming: &EnvNaming) -> EnvEntry {
+    EnvEntry::new(
+        &format!("{prefix}{}*", naming.separator),
+        std::any::type_name::<T>(),
+        default,
+    )
 }
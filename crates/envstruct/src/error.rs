@@ -1,3 +1,4 @@
+use crate::{strip_namespace, EnvParsePrimitive, REDACTED};
 use thiserror::Error;
 
 /// A boxed error type that is `Send`, `Sync`, and `'static`.
@@ -6,17 +7,29 @@ pub type BoxError = Box<dyn std::error::Error + Send + Sync + 'static>;
 const CAPTION: &str = "Configuration from environment variables failed";
 
 /// Represents errors that can occur while processing environment variables.
+///
+/// New kinds of errors may be added, matches need a wildcard arm.
 #[derive(Debug, Error)]
+#[non_exhaustive]
 pub enum EnvStructError {
     /// Error that occurs when an environment variable cannot be parsed.
     ///
     /// `var_name` is the name of the environment variable.
     /// `var_value` is the value of the environment variable.
+    /// `origin` describes where the value comes from, e.g. a file and line number.
+    /// `field` describes the field the variable is parsed into.
     /// `source` is the underlying error that caused this error.
-    #[error("{CAPTION}. `{var_name}` unable to parse value `{var_value}`, {source}")]
+    #[error(
+        "{CAPTION}. `{var_name}`{} unable to parse value `{var_value}`{}{}, {source}",
+        fmt_field_path(.field),
+        fmt_origin(.origin),
+        fmt_expected(.field)
+    )]
     ParseEnvError {
         var_name: String,
         var_value: String,
+        origin: Option<String>,
+        field: Box<FieldInfo>,
         #[source]
         source: BoxError,
     },
@@ -25,20 +38,83 @@ pub enum EnvStructError {
     ///
     /// `var_name` is the name of the environment variable.
     /// `var_value` is the default value of the environment variable.
+    /// `field` describes the field the variable is parsed into.
     /// `source` is the underlying error that caused this error.
-    #[error("{CAPTION}. `{var_name}` unable to parse default value `{var_value}`, {source}")]
+    #[error(
+        "{CAPTION}. `{var_name}`{} unable to parse default value `{var_value}`{}, {source}",
+        fmt_field_path(.field),
+        fmt_expected(.field)
+    )]
     ParseDefaultError {
         var_name: String,
         var_value: String,
+        field: Box<FieldInfo>,
         #[source]
         source: BoxError,
     },
 
     /// Error that occurs when an expected environment variable is missing.
     ///
-    /// The string is the name of the missing environment variable.
-    #[error("{CAPTION}. Environment variable `{0}` is not present")]
-    MissingEnvVar(String),
+    /// `var_name` is the name of the missing environment variable.
+    /// `suggestion` is the name of a present variable that looks like a misspelling of it.
+    /// `field` describes the field the variable is parsed into.
+    #[error(
+        "{CAPTION}. Environment variable `{var_name}`{} is not present{}{}",
+        fmt_field_path(.field),
+        fmt_expected(.field),
+        fmt_suggestion(.suggestion)
+    )]
+    MissingEnvVar {
+        var_name: String,
+        suggestion: Option<String>,
+        field: Box<FieldInfo>,
+    },
+
+    /// Error that occurs when a parsed value violates a constraint of its field.
+    ///
+    /// `var_name` is the name of the environment variable.
+    /// `rule` is the violated constraint, e.g. `min = 1`.
+    /// `message` describes the violation.
+    /// `field` describes the field the variable is parsed into.
+    #[error(
+        "{CAPTION}. `{var_name}`{} violates `{rule}`{}, {message}",
+        fmt_field_path(.field),
+        fmt_expected(.field)
+    )]
+    ValidationError {
+        var_name: String,
+        rule: String,
+        message: String,
+        field: Box<FieldInfo>,
+    },
+
+    /// Error that occurs when variables violate a relation between fields, declared with
+    /// `required_if`, `conflicts_with` or `group`.
+    ///
+    /// `rule` is the violated relation, e.g. `conflicts_with`.
+    /// `var_names` are the names of the variables involved.
+    /// `message` describes the violation.
+    #[error("{CAPTION}. {message}")]
+    RelationError {
+        rule: String,
+        var_names: Vec<String>,
+        message: String,
+    },
+
+    /// Error that occurs when the indices of the elements of a list have a gap.
+    ///
+    /// `var_name` is the prefix of the missing element, e.g. `APP_SERVERS_1`.
+    /// `next` is the prefix of the element set after the gap, e.g. `APP_SERVERS_2`.
+    /// `field` describes the field the list is parsed into.
+    #[error(
+        "{CAPTION}. No variables under `{var_name}`{} but under `{next}`, the elements of a list are numbered from 0 without gaps",
+        fmt_field_path(.field)
+    )]
+    IndexGap {
+        var_name: String,
+        next: String,
+        field: Box<FieldInfo>,
+    },
 
     /// Error that occurs when an environment variable key has an invalid format.
     ///
@@ -46,9 +122,379 @@ pub enum EnvStructError {
     #[error("{CAPTION}. Invalid key format `{0}`")]
     InvalidKeyFormat(String),
 
+    /// Error that occurs when several variables of a map have the same key after parsing, e.g.
+    /// `APP_LABELS_Team` and `APP_LABELS_TEAM` with lowercased keys.
+    ///
+    /// `var_names` are the names of the variables, sorted.
+    #[error("{CAPTION}. Variables {} have the same key", fmt_names(.var_names))]
+    DuplicateKey { var_names: Vec<String> },
+
     /// Error that occurs when an environment variable value has an invalid format.
     ///
     /// The string is the invalid value.
     #[error("{CAPTION}. Invalid environment value format `{0}`")]
     InvalidVarFormat(String),
+
+    /// Error that occurs in strict mode when variables under the prefix do not belong to any
+    /// field.
+    ///
+    /// The strings are the names of the unknown variables.
+    #[error("{CAPTION}. Unknown environment variables {}", fmt_names(.0))]
+    UnknownEnvVar(Vec<String>),
+
+    /// Error that occurs when a file cannot be read.
+    ///
+    /// `path` is the path of the file.
+    /// `source` is the underlying I/O error.
+    #[error("{CAPTION}. Unable to read `{path}`, {source}")]
+    ReadFileError {
+        path: String,
+        #[source]
+        source: std::io::Error,
+    },
+
+    /// Error that occurs when the file named by a `<NAME>_FILE` variable cannot be read.
+    ///
+    /// `var_name` is the name of the absent variable.
+    /// `file_var_name` is the name of the variable holding the path of the file.
+    /// `path` is the path of the file.
+    /// `field` describes the field the variable is parsed into.
+    /// `source` is the underlying I/O error.
+    #[error(
+        "{CAPTION}. `{var_name}`{} is not present and `{path}` named by `{file_var_name}` cannot be read, {source}",
+        fmt_field_path(.field)
+    )]
+    ReadVarFileError {
+        var_name: String,
+        file_var_name: String,
+        path: String,
+        field: Box<FieldInfo>,
+        #[source]
+        source: std::io::Error,
+    },
+
+    /// Error that occurs when a dotenv file has an invalid syntax.
+    ///
+    /// `path` is the path of the file.
+    /// `line` is the line number where the invalid entry starts.
+    /// `message` describes the problem.
+    #[error("{CAPTION}. Invalid syntax in `{path}` at line {line}, {message}")]
+    DotEnvSyntaxError {
+        path: String,
+        line: usize,
+        message: String,
+    },
+
+    /// Several errors that occurred while parsing different fields of a configuration.
+    #[error("{0}")]
+    Multiple(EnvStructErrors),
+}
+
+/// Describes the field of a configuration a variable is parsed into.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FieldInfo {
+    /// The path of the field, e.g. `Config.db.port`, empty if the variable is parsed outside of
+    /// a configuration struct.
+    pub path: String,
+    /// The name of the expected type without its namespace, e.g. `u16`.
+    pub typ: String,
+    /// A human readable hint on the expected format, e.g. `0-65535`.
+    pub hint: Option<String>,
+}
+
+impl FieldInfo {
+    /// Creates the description of a field of type `T`, the path is added as the error
+    /// propagates through the configuration structs.
+    pub fn of<T: EnvParsePrimitive>() -> Self {
+        Self::new(std::any::type_name::<T>(), T::hint())
+    }
+
+    /// Creates the description of a field with the given type name and format hint.
+    ///
+    /// # Arguments
+    ///
+    /// * `typ` - The fully qualified type name, the namespace is stripped.
+    /// * `hint` - A human readable hint on the expected format.
+    pub fn new(typ: &str, hint: Option<String>) -> Self {
+        Self {
+            path: String::new(),
+            typ: strip_namespace(typ),
+            hint,
+        }
+    }
+}
+
+fn fmt_field_path(field: &FieldInfo) -> String {
+    match field.path.is_empty() {
+        true => String::new(),
+        false => format!(" of `{}`", field.path),
+    }
+}
+
+fn fmt_expected(field: &FieldInfo) -> String {
+    match (field.typ.is_empty(), &field.hint) {
+        (true, _) => String::new(),
+        (false, None) => format!(", expected {}", field.typ),
+        (false, Some(hint)) => format!(", expected {} ({hint})", field.typ),
+    }
+}
+
+fn fmt_origin(origin: &Option<String>) -> String {
+    origin
+        .as_ref()
+        .map(|origin| format!(" from {origin}"))
+        .unwrap_or_default()
+}
+
+fn fmt_suggestion(suggestion: &Option<String>) -> String {
+    suggestion
+        .as_ref()
+        .map(|suggestion| format!(", did you mean `{suggestion}`?"))
+        .unwrap_or_default()
+}
+
+pub(crate) fn fmt_names(names: &[String]) -> String {
+    names
+        .iter()
+        .map(|name| format!("`{name}`"))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Stands in for the source of a redacted parse error.
+#[derive(Debug, Error)]
+#[error("details redacted")]
+struct RedactedSource;
+
+impl EnvStructError {
+    /// Replaces the values embedded in the error with a placeholder, so it can be printed
+    /// without leaking secrets.
+    ///
+    /// The source of a parse error is replaced as well, since parsers may quote the value in
+    /// their errors, e.g. ``unknown variant `x` ``. The expected type and hint are kept.
+    pub fn redact(self) -> EnvStructError {
+        match self {
+            EnvStructError::ParseEnvError {
+                var_name,
+                origin,
+                field,
+                ..
+            } => EnvStructError::ParseEnvError {
+                var_name,
+                var_value: REDACTED.to_string(),
+                origin,
+                field,
+                source: Box::new(RedactedSource),
+            },
+            EnvStructError::ParseDefaultError {
+                var_name, field, ..
+            } => EnvStructError::ParseDefaultError {
+                var_name,
+                var_value: REDACTED.to_string(),
+                field,
+                source: Box::new(RedactedSource),
+            },
+            EnvStructError::Multiple(errors) => EnvStructError::Multiple(EnvStructErrors(
+                errors.into_iter().map(EnvStructError::redact).collect(),
+            )),
+            err => err,
+        }
+    }
+
+    /// Prepends a segment to the path of the field the error belongs to.
+    ///
+    /// # Arguments
+    ///
+    /// * `segment` - The name of the field or struct, e.g. `db`.
+    pub fn in_field(mut self, segment: &str) -> EnvStructError {
+        if let EnvStructError::Multiple(errors) = self {
+            return EnvStructError::Multiple(EnvStructErrors(
+                errors
+                    .into_iter()
+                    .map(|err| err.in_field(segment))
+                    .collect(),
+            ));
+        }
+        if let Some(field) = self.field_mut() {
+            field.path = match field.path.is_empty() {
+                true => segment.to_string(),
+                false => format!("{segment}.{}", field.path),
+            };
+        }
+        self
+    }
+
+    /// Sets the expected type of the field the error belongs to, unless the error names it
+    /// already.
+    ///
+    /// # Arguments
+    ///
+    /// * `info` - The description of the type, its path is ignored.
+    pub fn with_type(mut self, info: &FieldInfo) -> EnvStructError {
+        if let EnvStructError::Multiple(errors) = self {
+            return EnvStructError::Multiple(EnvStructErrors(
+                errors.into_iter().map(|err| err.with_type(info)).collect(),
+            ));
+        }
+        if let Some(field) = self.field_mut().filter(|field| field.typ.is_empty()) {
+            field.typ.clone_from(&info.typ);
+            field.hint.clone_from(&info.hint);
+        }
+        self
+    }
+
+    /// Returns the description of the field the error belongs to, if any.
+    pub fn field(&self) -> Option<&FieldInfo> {
+        match self {
+            EnvStructError::ParseEnvError { field, .. }
+            | EnvStructError::ParseDefaultError { field, .. }
+            | EnvStructError::MissingEnvVar { field, .. }
+            | EnvStructError::ValidationError { field, .. }
+            | EnvStructError::IndexGap { field, .. }
+            | EnvStructError::ReadVarFileError { field, .. } => Some(field),
+            _ => None,
+        }
+    }
+
+    fn field_mut(&mut self) -> Option<&mut FieldInfo> {
+        match self {
+            EnvStructError::ParseEnvError { field, .. }
+            | EnvStructError::ParseDefaultError { field, .. }
+            | EnvStructError::MissingEnvVar { field, .. }
+            | EnvStructError::ValidationError { field, .. }
+            | EnvStructError::IndexGap { field, .. }
+            | EnvStructError::ReadVarFileError { field, .. } => Some(field),
+            _ => None,
+        }
+    }
+
+    /// Returns a stable code identifying the kind of the error, e.g. `missing_var`.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            EnvStructError::ParseEnvError { .. } => "parse_error",
+            EnvStructError::ParseDefaultError { .. } => "parse_default_error",
+            EnvStructError::MissingEnvVar { .. } => "missing_var",
+            EnvStructError::ValidationError { .. } => "validation_error",
+            EnvStructError::RelationError { .. } => "relation_error",
+            EnvStructError::IndexGap { .. } => "index_gap",
+            EnvStructError::InvalidKeyFormat(_) => "invalid_key_format",
+            EnvStructError::DuplicateKey { .. } => "duplicate_key",
+            EnvStructError::InvalidVarFormat(_) => "invalid_var_format",
+            EnvStructError::UnknownEnvVar(_) => "unknown_vars",
+            EnvStructError::ReadFileError { .. } => "read_file_error",
+            EnvStructError::ReadVarFileError { .. } => "read_var_file_error",
+            EnvStructError::DotEnvSyntaxError { .. } => "dotenv_syntax_error",
+            EnvStructError::Multiple(_) => "multiple",
+        }
+    }
+
+    /// Returns the first error if this error holds several errors, otherwise returns itself.
+    pub fn into_first(self) -> EnvStructError {
+        match self {
+            EnvStructError::Multiple(mut errors) if !errors.is_empty() => errors.0.remove(0),
+            err => err,
+        }
+    }
+}
+
+/// A collection of all errors that occurred while parsing a configuration.
+#[derive(Debug, Default)]
+pub struct EnvStructErrors(Vec<EnvStructError>);
+
+impl EnvStructErrors {
+    /// Creates an empty collection of errors.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds an error to the collection, flattening `EnvStructError::Multiple`.
+    ///
+    /// # Arguments
+    ///
+    /// * `err` - The error to add.
+    pub fn push(&mut self, err: EnvStructError) {
+        match err {
+            EnvStructError::Multiple(errors) => self.0.extend(errors.0),
+            err => self.0.push(err),
+        }
+    }
+
+    /// Takes the value of a result, adding its error to the collection.
+    ///
+    /// # Arguments
+    ///
+    /// * `result` - The result to inspect.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(value)` if the result is `Ok`.
+    /// * `None` if the result is `Err`.
+    pub fn collect<T>(&mut self, result: Result<T, EnvStructError>) -> Option<T> {
+        result.map_err(|err| self.push(err)).ok()
+    }
+
+    /// Returns `true` if the collection contains no errors.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns the number of errors in the collection.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns an iterator over the errors.
+    pub fn iter(&self) -> std::slice::Iter<'_, EnvStructError> {
+        self.0.iter()
+    }
+}
+
+impl std::fmt::Display for EnvStructErrors {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (i, err) in self.0.iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "{err}")?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for EnvStructErrors {}
+
+impl From<EnvStructError> for EnvStructErrors {
+    fn from(err: EnvStructError) -> Self {
+        let mut errors = Self::new();
+        errors.push(err);
+        errors
+    }
+}
+
+impl From<EnvStructErrors> for EnvStructError {
+    /// Converts the collection into a single error, unwrapping it if it holds only one error.
+    fn from(mut errors: EnvStructErrors) -> Self {
+        match errors.len() {
+            1 => errors.0.remove(0),
+            _ => EnvStructError::Multiple(errors),
+        }
+    }
+}
+
+impl IntoIterator for EnvStructErrors {
+    type Item = EnvStructError;
+    type IntoIter = std::vec::IntoIter<EnvStructError>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a EnvStructErrors {
+    type Item = &'a EnvStructError;
+    type IntoIter = std::slice::Iter<'a, EnvStructError>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
 }
@@ -0,0 +1,140 @@
+#![cfg(feature = "serde_json")]
+
+use crate::*;
+use serde::ser::{Serialize, SerializeMap, Serializer};
+
+/// Serializes an error to a JSON object for deployment tooling.
+///
+/// Every object has a `kind` code (see [`EnvStructError::kind`]) and the human readable
+/// `message`. The other keys depend on the kind:
+///
+/// * `parse_error`, `parse_default_error`: `var_name`, `value`, `field_path`, `type`, `hint`, and
+///   `origin` for `parse_error`. The value is redacted for secrets.
+/// * `missing_var`: `var_name`, `field_path`, `type`, `hint`, `suggestion`.
+/// * `validation_error`: `var_name`, `rule`, `description`, `field_path`, `type`, `hint`.
+/// * `relation_error`: `rule`, `var_names`, `description`.
+/// * `index_gap`: `var_name`, `next`, `field_path`, `type`, `hint`.
+/// * `duplicate_key`: `var_names`.
+/// * `invalid_key_format`, `invalid_var_format`: `value`.
+/// * `unknown_vars`: `var_names`.
+/// * `read_file_error`: `path`.
+/// * `read_var_file_error`: `var_name`, `file_var_name`, `path`, `field_path`, `type`, `hint`.
+/// * `dotenv_syntax_error`: `path`, `line`.
+/// * `multiple`: `errors`, the list of the aggregated errors.
+///
+/// Absent optional values are `null`, an empty `field_path` means the variable was parsed
+/// outside of a configuration struct.
+///
+/// ```
+/// use envstruct::prelude::*;
+///
+/// let source = MapSource::new([("PORT", "http")]);
+/// let err = u16::parse_from_source(&source, "PORT", None).unwrap_err();
+/// let json = serde_json::to_value(&err)?;
+/// assert_eq!(json["kind"], "parse_error");
+/// assert_eq!(json["var_name"], "PORT");
+/// assert_eq!(json["type"], "u16");
+/// # Ok::<(), serde_json::Error>(())
+/// ```
+impl Serialize for EnvStructError {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(None)?;
+        map.serialize_entry("kind", self.kind())?;
+        map.serialize_entry("message", &self.to_string())?;
+
+        match self {
+            EnvStructError::ParseEnvError {
+                var_name,
+                var_value,
+                origin,
+                ..
+            } => {
+                map.serialize_entry("var_name", var_name)?;
+                map.serialize_entry("value", var_value)?;
+                map.serialize_entry("origin", origin)?;
+            }
+            EnvStructError::ParseDefaultError {
+                var_name,
+                var_value,
+                ..
+            } => {
+                map.serialize_entry("var_name", var_name)?;
+                map.serialize_entry("value", var_value)?;
+            }
+            EnvStructError::MissingEnvVar {
+                var_name,
+                suggestion,
+                ..
+            } => {
+                map.serialize_entry("var_name", var_name)?;
+                map.serialize_entry("suggestion", suggestion)?;
+            }
+            EnvStructError::ValidationError {
+                var_name,
+                rule,
+                message,
+                ..
+            } => {
+                map.serialize_entry("var_name", var_name)?;
+                map.serialize_entry("rule", rule)?;
+                map.serialize_entry("description", message)?;
+            }
+            EnvStructError::RelationError {
+                rule,
+                var_names,
+                message,
+            } => {
+                map.serialize_entry("rule", rule)?;
+                map.serialize_entry("var_names", var_names)?;
+                map.serialize_entry("description", message)?;
+            }
+            EnvStructError::IndexGap { var_name, next, .. } => {
+                map.serialize_entry("var_name", var_name)?;
+                map.serialize_entry("next", next)?;
+            }
+            EnvStructError::DuplicateKey { var_names } => {
+                map.serialize_entry("var_names", var_names)?;
+            }
+            EnvStructError::InvalidKeyFormat(value) | EnvStructError::InvalidVarFormat(value) => {
+                map.serialize_entry("value", value)?;
+            }
+            EnvStructError::UnknownEnvVar(var_names) => {
+                map.serialize_entry("var_names", var_names)?;
+            }
+            EnvStructError::ReadFileError { path, .. } => {
+                map.serialize_entry("path", path)?;
+            }
+            EnvStructError::ReadVarFileError {
+                var_name,
+                file_var_name,
+                path,
+                ..
+            } => {
+                map.serialize_entry("var_name", var_name)?;
+                map.serialize_entry("file_var_name", file_var_name)?;
+                map.serialize_entry("path", path)?;
+            }
+            EnvStructError::DotEnvSyntaxError { path, line, .. } => {
+                map.serialize_entry("path", path)?;
+                map.serialize_entry("line", line)?;
+            }
+            EnvStructError::Multiple(errors) => {
+                map.serialize_entry("errors", errors)?;
+            }
+        }
+
+        if let Some(field) = self.field() {
+            map.serialize_entry("field_path", &field.path)?;
+            map.serialize_entry("type", &field.typ)?;
+            map.serialize_entry("hint", &field.hint)?;
+        }
+        map.end()
+    }
+}
+
+/// Serializes the errors to a JSON array of error objects.
+impl Serialize for EnvStructErrors {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self)
+    }
+}
@@ -0,0 +1,116 @@
+use crate::*;
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Parses the elements of a list from indexed variables, e.g. `APP_SERVERS_0_HOST` and
+/// `APP_SERVERS_1_HOST`. The indices must be numbered from 0 without gaps, a gap is reported as
+/// `EnvStructError::IndexGap`. Use a `BTreeMap<usize, T>` for sparse indices.
+impl<T: EnvParseNested> EnvParseNested for Vec<T> {
+    fn parse_from_source_named(
+        source: &dyn EnvSource,
+        var_name: impl AsRef<str>,
+        _default: Option<&str>,
+        naming: &EnvNaming,
+    ) -> Result<Self, EnvStructError>
+    where
+        Self: Sized,
+    {
+        let var_name = var_name.as_ref();
+        let indices = element_indices(source, var_name, naming);
+        if let Some((gap, next)) = (0..)
+            .zip(&indices)
+            .find(|(expected, index)| expected != *index)
+        {
+            return Err(EnvStructError::IndexGap {
+                var_name: naming.join(var_name, gap.to_string()),
+                next: naming.join(var_name, next.to_string()),
+                field: Box::new(FieldInfo::new(std::any::type_name::<Self>(), None)),
+            });
+        }
+        Ok(parse_elements(source, var_name, naming, indices)?
+            .into_values()
+            .collect())
+    }
+
+    fn get_env_entries_named(
+        prefix: impl AsRef<str>,
+        _default: Option<&str>,
+        naming: &EnvNaming,
+    ) -> Result<Vec<EnvEntry>, EnvStructError> {
+        T::get_env_entries_named(naming.join(prefix, INDEX_PLACEHOLDER), None, naming)
+    }
+}
+
+/// Parses the elements of a list from indexed variables keyed by their index, e.g.
+/// `APP_SERVERS_0_HOST` and `APP_SERVERS_5_HOST`. Unlike a `Vec<T>`, the indices may have gaps.
+impl<T: EnvParseNested> EnvParseNested for BTreeMap<usize, T> {
+    fn parse_from_source_named(
+        source: &dyn EnvSource,
+        var_name: impl AsRef<str>,
+        _default: Option<&str>,
+        naming: &EnvNaming,
+    ) -> Result<Self, EnvStructError>
+    where
+        Self: Sized,
+    {
+        let var_name = var_name.as_ref();
+        parse_elements(
+            source,
+            var_name,
+            naming,
+            element_indices(source, var_name, naming),
+        )
+    }
+
+    fn get_env_entries_named(
+        prefix: impl AsRef<str>,
+        _default: Option<&str>,
+        naming: &EnvNaming,
+    ) -> Result<Vec<EnvEntry>, EnvStructError> {
+        T::get_env_entries_named(naming.join(prefix, INDEX_PLACEHOLDER), None, naming)
+    }
+}
+
+/// Finds the indices of the elements set under a prefix, e.g. `0` and `1` for
+/// `APP_SERVERS_0_HOST` and `APP_SERVERS_1_PORT` under `APP_SERVERS`.
+///
+/// Indices with leading zeros are ignored, they would not be read by the element.
+fn element_indices(source: &dyn EnvSource, prefix: &str, naming: &EnvNaming) -> BTreeSet<usize> {
+    let start = format!("{prefix}{}", naming.separator);
+    source
+        .var_names(&start)
+        .iter()
+        .filter_map(|name| {
+            let rest = name.strip_prefix(&start)?;
+            let digits = rest.split(naming.separator).next()?;
+            let index: usize = digits.parse().ok()?;
+            (index.to_string() == digits).then_some(index)
+        })
+        .collect()
+}
+
+/// Parses the elements with the given indices, collecting the errors of all elements.
+fn parse_elements<T: EnvParseNested>(
+    source: &dyn EnvSource,
+    prefix: &str,
+    naming: &EnvNaming,
+    indices: BTreeSet<usize>,
+) -> Result<BTreeMap<usize, T>, EnvStructError> {
+    let mut errors = EnvStructErrors::new();
+    let mut elements = BTreeMap::new();
+    for index in indices {
+        let element = T::parse_from_source_named(
+            source,
+            naming.join(prefix, index.to_string()),
+            None,
+            naming,
+        )
+        .map_err(|err| err.in_field(&index.to_string()));
+        if let Some(element) = errors.collect(element) {
+            elements.insert(index, element);
+        }
+    }
+    match errors.is_empty() {
+        true => Ok(elements),
+        false => Err(errors.into()),
+    }
+}
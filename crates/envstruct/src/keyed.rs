@@ -0,0 +1,71 @@
+use crate::*;
+use std::{
+    collections::{BTreeSet, HashMap},
+    str::FromStr,
+};
+
+/// Parses a map of nested values from variables named by key, e.g. `APP_DB_TENANT1_HOST` and
+/// `APP_DB_TENANT2_HOST`.
+///
+/// The keys are found by matching the variables under the prefix against the names of the
+/// entries of the value type, e.g. `APP_DB_<NAME>_HOST`. A key may contain the separator, if a
+/// variable matches several entries the shortest key is taken.
+impl<K, V> EnvParseNested for HashMap<K, V>
+where
+    K: FromStr + std::hash::Hash + std::cmp::Eq,
+    V: EnvParseNested,
+{
+    fn parse_from_source_named(
+        source: &dyn EnvSource,
+        var_name: impl AsRef<str>,
+        _default: Option<&str>,
+        naming: &EnvNaming,
+    ) -> Result<Self, EnvStructError>
+    where
+        Self: Sized,
+    {
+        let var_name = var_name.as_ref();
+        let entries = Self::get_env_entries_named(var_name, None, naming)?;
+        let keys: BTreeSet<_> = source
+            .var_names(&format!("{var_name}{}", naming.separator))
+            .iter()
+            .filter_map(|name| {
+                entries
+                    .iter()
+                    .flat_map(EnvEntry::names)
+                    .filter_map(|pattern| match_key(pattern, name))
+                    .min_by_key(|key| key.len())
+                    .map(ToString::to_string)
+            })
+            .collect();
+
+        let mut errors = EnvStructErrors::new();
+        let mut map = HashMap::new();
+        for key in keys {
+            let prefix = format!("{var_name}{}{key}", naming.separator);
+            let parsed_key = errors.collect(
+                K::from_str(&key).map_err(|_| EnvStructError::InvalidKeyFormat(prefix.clone())),
+            );
+            let naming = naming.with_verbatim_prefix(&prefix);
+            let value = errors.collect(
+                V::parse_from_source_named(source, &prefix, None, &naming)
+                    .map_err(|err| err.in_field(&key)),
+            );
+            if let (Some(key), Some(value)) = (parsed_key, value) {
+                map.insert(key, value);
+            }
+        }
+        match errors.is_empty() {
+            true => Ok(map),
+            false => Err(errors.into()),
+        }
+    }
+
+    fn get_env_entries_named(
+        prefix: impl AsRef<str>,
+        _default: Option<&str>,
+        naming: &EnvNaming,
+    ) -> Result<Vec<EnvEntry>, EnvStructError> {
+        V::get_env_entries_named(naming.join(prefix, KEY_PLACEHOLDER), None, naming)
+    }
+}
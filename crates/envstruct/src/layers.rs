@@ -0,0 +1,220 @@
+use crate::*;
+use prettytable::{format, Cell, Row, Table};
+
+/// A stack of named sources with explicit precedence.
+///
+/// Layers are added from the lowest to the highest precedence, each lookup falls through the
+/// stack from the top until a layer has the variable.
+///
+/// ```
+/// use envstruct::prelude::*;
+///
+/// let layers = Layers::new()
+///     .layer("defaults", MapSource::new([("APP_PORT", "80"), ("APP_HOST", "localhost")]))
+///     .layer("process env", ProcessEnv)
+///     .layer("overrides", MapSource::new([("APP_PORT", "8080")]));
+///
+/// assert_eq!(layers.var("APP_PORT").unwrap().as_deref(), Some("8080"));
+/// assert_eq!(layers.origin("APP_HOST").as_deref(), Some("defaults"));
+/// ```
+#[derive(Default)]
+pub struct Layers<'a> {
+    layers: Vec<(String, Box<dyn EnvSource + 'a>)>,
+}
+
+impl<'a> Layers<'a> {
+    /// Creates an empty stack of layers.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a layer on top of the stack, taking precedence over all previously added layers.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the layer, reported as the origin of its variables.
+    /// * `source` - The source of the layer.
+    pub fn layer(mut self, name: impl Into<String>, source: impl EnvSource + 'a) -> Self {
+        self.layers.push((name.into(), Box::new(source)));
+        self
+    }
+
+    /// Returns the name of the layer that supplies a variable.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the variable.
+    pub fn layer_of(&self, name: &str) -> Option<&str> {
+        self.find(name).map(|(layer, _)| layer.as_str())
+    }
+
+    /// Finds the topmost layer that has a variable.
+    fn find(&self, name: &str) -> Option<&(String, Box<dyn EnvSource + 'a>)> {
+        self.layers
+            .iter()
+            .rev()
+            .find(|(_, source)| source.contains(name))
+    }
+}
+
+impl EnvSource for Layers<'_> {
+    fn var(&self, name: &str) -> Result<Option<String>, EnvStructError> {
+        match self.find(name) {
+            Some((_, source)) => source.var(name),
+            None => Ok(None),
+        }
+    }
+
+    fn var_names(&self, prefix: &str) -> Vec<String> {
+        let mut names: Vec<_> = self
+            .layers
+            .iter()
+            .flat_map(|(_, source)| source.var_names(prefix))
+            .collect();
+        names.sort();
+        names.dedup();
+        names
+    }
+
+    fn contains(&self, name: &str) -> bool {
+        self.find(name).is_some()
+    }
+
+    fn origin(&self, name: &str) -> Option<String> {
+        self.find(name)
+            .map(|(layer, source)| match source.origin(name) {
+                Some(origin) => format!("{layer} ({origin})"),
+                None => layer.to_string(),
+            })
+    }
+}
+
+/// Describes where the final value of a variable comes from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Provenance {
+    /// The value is supplied by the source.
+    ///
+    /// The string describes where the value comes from, e.g. the name of a layer, if the source
+    /// can tell it.
+    Source(Option<String>),
+    /// The variable is absent and the default value of the field is used.
+    Default,
+    /// The variable is absent and there is no default value.
+    Missing,
+}
+
+impl std::fmt::Display for Provenance {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Provenance::Source(Some(origin)) => write!(f, "{origin}"),
+            Provenance::Source(None) => write!(f, "source"),
+            Provenance::Default => write!(f, "default"),
+            Provenance::Missing => write!(f, "missing"),
+        }
+    }
+}
+
+/// A trait for reporting where the values of a configuration come from.
+pub trait EnvStructProvenance: EnvParseNested {
+    /// Reports for every variable of the configuration where its final value comes from.
+    ///
+    /// Wildcard entries, e.g. of an `EnvMap`, and the entries of lists and maps of structs, e.g.
+    /// `APP_SERVERS_<N>_HOST`, are expanded to the matching variables of the source.
+    ///
+    /// # Arguments
+    ///
+    /// * `source` - The source the configuration is parsed from.
+    /// * `prefix` - A prefix for the variables.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `EnvStructError` if the entries cannot be retrieved.
+    fn provenance(
+        source: &dyn EnvSource,
+        prefix: impl AsRef<str>,
+    ) -> Result<Vec<(EnvEntry, Provenance)>, EnvStructError> {
+        let mut result = Vec::new();
+        for entry in Self::get_env_entries(prefix, None)? {
+            if let Some(names) = entry.expand(source) {
+                for name in names {
+                    let provenance = Provenance::Source(entry.source(source).origin(&name));
+                    result.push((
+                        EnvEntry {
+                            name,
+                            ..entry.clone()
+                        },
+                        provenance,
+                    ));
+                }
+                continue;
+            }
+
+            // the entry is listed under the name of the alias the value is read from
+            let entry_source = entry.source(source);
+            let present = entry
+                .names()
+                .find(|name| entry_source.contains(name))
+                .cloned();
+            if let Some(name) = present {
+                let provenance = Provenance::Source(entry_source.origin(&name));
+                result.push((EnvEntry { name, ..entry }, provenance));
+                continue;
+            }
+
+            let provenance = if entry.default.is_some() {
+                Provenance::Default
+            } else {
+                Provenance::Missing
+            };
+            result.push((entry, provenance));
+        }
+        Ok(result)
+    }
+
+    /// Generates a table of the final values of the configuration and where they come from.
+    ///
+    /// Values of secret entries are masked.
+    ///
+    /// # Arguments
+    ///
+    /// * `source` - The source the configuration is parsed from.
+    /// * `prefix` - A prefix for the variables.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `EnvStructError` if the entries or values cannot be retrieved.
+    fn dump(source: &dyn EnvSource, prefix: impl AsRef<str>) -> Result<String, EnvStructError> {
+        let mut table = Table::new();
+        table.set_titles(Row::new(vec![
+            Cell::new("NAME"),
+            Cell::new("VALUE"),
+            Cell::new("ORIGIN"),
+        ]));
+
+        for (entry, provenance) in Self::provenance(source, prefix)? {
+            let value = match provenance {
+                Provenance::Source(_) => entry.source(source).var(&entry.name)?,
+                Provenance::Default => entry.default,
+                Provenance::Missing => None,
+            };
+            table.add_row(Row::new(vec![
+                Cell::new(&entry.name),
+                Cell::new(
+                    &value
+                        .map(|v| match entry.secret {
+                            true => REDACTED.to_string(),
+                            false => format!(r#""{v}""#),
+                        })
+                        .unwrap_or_default(),
+                ),
+                Cell::new(&provenance.to_string()),
+            ]));
+        }
+
+        table.set_format(*format::consts::FORMAT_NO_BORDER_LINE_SEPARATOR);
+
+        Ok(table.to_string())
+    }
+}
+
+impl<T: EnvParseNested> EnvStructProvenance for T {}
@@ -1,25 +1,50 @@
 #![allow(internal_features)]
 
+mod dotenv;
 mod env_json;
 mod env_map;
 mod error;
+mod error_json;
+mod indexed;
+mod keyed;
+mod layers;
+mod naming;
 mod parse_nested;
 mod parse_primitive;
+mod relation;
+mod secret;
+mod secrets_dir;
+mod source;
+mod suggest;
 mod usage;
+mod validate;
+mod variants;
+mod warning;
 mod with_json;
 
+pub use dotenv::*;
 pub use error::*;
+pub use layers::*;
+pub use naming::*;
 pub use parse_nested::*;
 pub use parse_primitive::*;
+pub use relation::*;
+pub use secret::*;
+pub use secrets_dir::*;
+pub use source::*;
 pub use usage::*;
+pub use validate::*;
+pub use variants::*;
+pub use warning::*;
 
 pub use envstruct_derive::*;
 
 /// The `prelude` module re-exports common items for easy inclusion.
 pub mod prelude {
     pub use super::{
-        env_json::*, env_map::*, error::*, parse_nested::*, parse_primitive::*, usage::*,
-        with_json::*,
+        dotenv::*, env_json::*, env_map::*, error::*, layers::*, naming::*, parse_nested::*,
+        parse_primitive::*, relation::*, secret::*, secrets_dir::*, source::*, usage::*,
+        validate::*, variants::*, warning::*, with_json::*,
     };
     pub use envstruct_derive::*;
 }
@@ -0,0 +1,138 @@
+/// The case of the variable names derived from the field names.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NameCase {
+    /// `MY_APP_DB_HOST`, the default with the `env_uppercase` feature.
+    ScreamingSnake,
+    /// The names as written, e.g. `MY_APP_db_host` for the prefix `MY_APP`.
+    Preserve,
+    /// `my_app_db_host`.
+    Lower,
+    /// `db-host`, the prefix is lowercased, e.g. `my_app-db-host` for the prefix `MY_APP`.
+    Kebab,
+}
+
+impl NameCase {
+    /// Converts the name of a field to the case, its words are separated by `_`.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name to convert.
+    pub fn convert(&self, name: &str) -> String {
+        match self {
+            NameCase::Kebab => name.to_lowercase().replace('_', "-"),
+            _ => self.convert_prefix(name),
+        }
+    }
+
+    /// Converts a prefix to the case, keeping its separators as written.
+    ///
+    /// # Arguments
+    ///
+    /// * `prefix` - The prefix to convert.
+    pub fn convert_prefix(&self, prefix: &str) -> String {
+        match self {
+            NameCase::ScreamingSnake => prefix.to_uppercase(),
+            NameCase::Preserve => prefix.to_string(),
+            NameCase::Lower | NameCase::Kebab => prefix.to_lowercase(),
+        }
+    }
+}
+
+/// The naming policy of variables: the case of the names and the separator between the prefix
+/// and the name of a field.
+///
+/// Structs declare it with `#[env(rename_all = "...", separator = "...")]`, nested structs
+/// inherit the policy of their parent unless they declare their own.
+///
+/// ```
+/// use envstruct::prelude::*;
+///
+/// let naming = EnvNaming::default().with_case(NameCase::Lower).with_separator("__");
+/// assert_eq!(naming.join("APP", "db_host"), "app__db_host");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EnvNaming {
+    /// The case of the names.
+    pub case: NameCase,
+    /// The separator between the prefix and the name of a field.
+    pub separator: &'static str,
+    /// The length of the start of the prefixes kept as written, e.g. `APP_DB_tenant1` for the
+    /// elements of a map keyed by name.
+    verbatim: usize,
+}
+
+impl Default for EnvNaming {
+    /// Uppercase names separated by `_` with the `env_uppercase` feature, otherwise the names as
+    /// written separated by `_`.
+    fn default() -> Self {
+        Self {
+            #[cfg(feature = "env_uppercase")]
+            case: NameCase::ScreamingSnake,
+            #[cfg(not(feature = "env_uppercase"))]
+            case: NameCase::Preserve,
+            separator: "_",
+            verbatim: 0,
+        }
+    }
+}
+
+impl EnvNaming {
+    /// Sets the case of the names.
+    ///
+    /// `NameCase::Kebab` also sets the separator to `-` if it is `_`.
+    ///
+    /// # Arguments
+    ///
+    /// * `case` - The case of the names.
+    pub fn with_case(mut self, case: NameCase) -> Self {
+        self.case = case;
+        if case == NameCase::Kebab && self.separator == "_" {
+            self.separator = "-";
+        }
+        self
+    }
+
+    /// Sets the separator between the prefix and the name of a field.
+    ///
+    /// # Arguments
+    ///
+    /// * `separator` - The separator, e.g. `__`.
+    pub fn with_separator(mut self, separator: &'static str) -> Self {
+        self.separator = separator;
+        self
+    }
+
+    /// Keeps the case of the start of the prefixes, so keys found in variable names are not
+    /// converted when the names of the fields are joined to them.
+    ///
+    /// # Arguments
+    ///
+    /// * `prefix` - The start of the prefixes to keep as written.
+    pub(crate) fn with_verbatim_prefix(mut self, prefix: &str) -> Self {
+        self.verbatim = prefix.len();
+        self
+    }
+
+    /// Joins a prefix and the name of a field into a variable name.
+    ///
+    /// The case of the prefix is converted, but not its separators, so the names of nested
+    /// structs keep the separators of their parents.
+    ///
+    /// # Arguments
+    ///
+    /// * `prefix` - The prefix of the variable, may be empty.
+    /// * `name` - The name of the field, may be empty.
+    pub fn join(&self, prefix: impl AsRef<str>, name: impl AsRef<str>) -> String {
+        let prefix = prefix.as_ref();
+        let prefix = match prefix.split_at_checked(self.verbatim) {
+            Some((verbatim, rest)) => format!("{verbatim}{}", self.case.convert_prefix(rest)),
+            None => self.case.convert_prefix(prefix),
+        };
+        let name = self.case.convert(name.as_ref());
+        match (prefix.is_empty(), name.is_empty()) {
+            (false, true) => prefix,
+            (true, false) => name,
+            _ => format!("{prefix}{}{name}", self.separator),
+        }
+    }
+}
@@ -3,7 +3,8 @@ use paste::paste;
 
 /// Trait for parsing nested environment variables.
 pub trait EnvParseNested {
-    /// Creates a new instance by parsing environment variables.
+    /// Creates a new instance by parsing environment variables under the prefix declared with
+    /// `#[env(prefix = "...")]`, if any.
     ///
     /// # Errors
     ///
@@ -12,7 +13,15 @@ pub trait EnvParseNested {
     where
         Self: Sized,
     {
-        Self::parse_from_env_var("", None)
+        Self::from_source(&ProcessEnv, Self::env_prefix().unwrap_or_default())
+    }
+
+    /// Returns the prefix declared with `#[env(prefix = "...")]`.
+    ///
+    /// It is the default prefix of `new` and `usage`, and replaces the field name in the
+    /// variable names when the type is nested in another struct.
+    fn env_prefix() -> Option<&'static str> {
+        None
     }
 
     /// Creates a new instance with a specified prefix by parsing environment variables.
@@ -28,7 +37,181 @@ pub trait EnvParseNested {
     where
         Self: Sized,
     {
-        Self::parse_from_env_var(prefix, None)
+        Self::from_source(&ProcessEnv, prefix)
+    }
+
+    /// Creates a new instance with a specified prefix by parsing variables from the given source.
+    ///
+    /// # Arguments
+    ///
+    /// * `source` - The source to look the variables up in.
+    /// * `prefix` - A prefix for the variables.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `EnvStructError` if parsing fails.
+    fn from_source(source: &dyn EnvSource, prefix: impl AsRef<str>) -> Result<Self, EnvStructError>
+    where
+        Self: Sized,
+    {
+        Self::from_source_all_errors(source, prefix)
+            .map_err(|errors| EnvStructError::from(errors).into_first())
+    }
+
+    /// Creates a new instance with a specified prefix by parsing environment variables,
+    /// reporting every invalid variable instead of only the first one.
+    ///
+    /// # Arguments
+    ///
+    /// * `prefix` - A prefix for the environment variables.
+    ///
+    /// # Errors
+    ///
+    /// Returns `EnvStructErrors` with all errors if parsing fails.
+    fn with_prefix_all_errors(prefix: impl AsRef<str>) -> Result<Self, EnvStructErrors>
+    where
+        Self: Sized,
+    {
+        Self::from_source_all_errors(&ProcessEnv, prefix)
+    }
+
+    /// Creates a new instance with a specified prefix by parsing variables from the given source,
+    /// reporting every invalid variable instead of only the first one.
+    ///
+    /// Errors of missing variables suggest present variables with similar names.
+    ///
+    /// # Arguments
+    ///
+    /// * `source` - The source to look the variables up in.
+    /// * `prefix` - A prefix for the variables.
+    ///
+    /// # Errors
+    ///
+    /// Returns `EnvStructErrors` with all errors if parsing fails.
+    fn from_source_all_errors(
+        source: &dyn EnvSource,
+        prefix: impl AsRef<str>,
+    ) -> Result<Self, EnvStructErrors>
+    where
+        Self: Sized,
+    {
+        let prefix = prefix.as_ref();
+        Self::parse_from_source(source, prefix, None).map_err(|err| {
+            let err = err.in_field(&strip_namespace(std::any::type_name::<Self>()));
+            let entries = Self::get_env_entries(prefix, None).unwrap_or_default();
+            crate::suggest::add_suggestions(err.into(), source, &entries)
+        })
+    }
+
+    /// Creates a new instance with a specified prefix by parsing variables from the given source,
+    /// returning the warnings reported while parsing, e.g. deprecated variables that are set.
+    ///
+    /// # Arguments
+    ///
+    /// * `source` - The source to look the variables up in.
+    /// * `prefix` - A prefix for the variables.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `EnvStructError` if parsing fails.
+    fn from_source_with_warnings(
+        source: &dyn EnvSource,
+        prefix: impl AsRef<str>,
+    ) -> Result<(Self, Vec<EnvWarning>), EnvStructError>
+    where
+        Self: Sized,
+    {
+        let collector = WarningCollector {
+            source,
+            warnings: Default::default(),
+        };
+        let value = Self::from_source(&collector, prefix)?;
+        Ok((value, collector.warnings.into_inner()))
+    }
+
+    /// Creates a new instance with a specified prefix by parsing environment variables, rejecting
+    /// variables under the prefix that do not belong to any field.
+    ///
+    /// # Arguments
+    ///
+    /// * `prefix` - A prefix for the environment variables.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `EnvStructError` if parsing fails or `EnvStructError::UnknownEnvVar` if there
+    /// are unknown variables.
+    fn with_prefix_strict(prefix: impl AsRef<str>) -> Result<Self, EnvStructError>
+    where
+        Self: Sized,
+    {
+        Self::from_source_strict(&ProcessEnv, prefix)
+    }
+
+    /// Creates a new instance with a specified prefix by parsing variables from the given source,
+    /// rejecting variables under the prefix that do not belong to any field.
+    ///
+    /// # Arguments
+    ///
+    /// * `source` - The source to look the variables up in.
+    /// * `prefix` - A prefix for the variables.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `EnvStructError` if parsing fails or `EnvStructError::UnknownEnvVar` if there
+    /// are unknown variables.
+    fn from_source_strict(
+        source: &dyn EnvSource,
+        prefix: impl AsRef<str>,
+    ) -> Result<Self, EnvStructError>
+    where
+        Self: Sized,
+    {
+        let prefix = prefix.as_ref();
+        let value = Self::from_source(source, prefix)?;
+        let unknown = Self::unknown_vars(source, prefix)?;
+        if !unknown.is_empty() {
+            return Err(EnvStructError::UnknownEnvVar(unknown));
+        }
+        Ok(value)
+    }
+
+    /// Returns the names of the variables under the prefix that do not belong to any field.
+    ///
+    /// Wildcard entries, e.g. of an `EnvMap`, match every variable starting with them, and
+    /// `<NAME>_FILE` variables are accepted for every field. An empty prefix covers every
+    /// variable of the source.
+    ///
+    /// # Arguments
+    ///
+    /// * `source` - The source to look the variables up in.
+    /// * `prefix` - A prefix for the variables.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `EnvStructError` if the entries cannot be retrieved.
+    fn unknown_vars(
+        source: &dyn EnvSource,
+        prefix: impl AsRef<str>,
+    ) -> Result<Vec<String>, EnvStructError> {
+        let prefix = prefix.as_ref();
+        let entries = Self::get_env_entries(prefix, None)?;
+        let matches = |name: &str| entries.iter().any(|entry| entry.matches(name));
+        let is_known =
+            |name: &str| matches(name) || name.strip_suffix("_FILE").is_some_and(matches);
+
+        let mut unknown: Vec<_> = source
+            .var_names(prefix)
+            .into_iter()
+            // only match at a separator, `APP_MAPX` is not under the prefix `APP_MAP`
+            .filter(|name| {
+                prefix.is_empty()
+                    || name == prefix
+                    || name[prefix.len()..].starts_with(|c: char| !c.is_alphanumeric())
+            })
+            .filter(|name| !is_known(name))
+            .collect();
+        unknown.sort();
+        Ok(unknown)
     }
 
     /// Parses the environment variable with an optional default value.
@@ -45,6 +228,53 @@ pub trait EnvParseNested {
         var_name: impl AsRef<str>,
         default: Option<&str>,
     ) -> Result<Self, EnvStructError>
+    where
+        Self: Sized,
+    {
+        Self::parse_from_source(&ProcessEnv, var_name, default)
+    }
+
+    /// Parses the variables from the given source with an optional default value.
+    ///
+    /// # Arguments
+    ///
+    /// * `source` - The source to look the variables up in.
+    /// * `var_name` - The name of the variable.
+    /// * `default` - An optional default value.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `EnvStructError` if parsing fails.
+    fn parse_from_source(
+        source: &dyn EnvSource,
+        var_name: impl AsRef<str>,
+        default: Option<&str>,
+    ) -> Result<Self, EnvStructError>
+    where
+        Self: Sized,
+    {
+        Self::parse_from_source_named(source, var_name, default, &EnvNaming::default())
+    }
+
+    /// Parses the variables from the given source, naming them with the policy inherited from
+    /// the parent struct unless the type declares its own.
+    ///
+    /// # Arguments
+    ///
+    /// * `source` - The source to look the variables up in.
+    /// * `var_name` - The name of the variable.
+    /// * `default` - An optional default value.
+    /// * `naming` - The naming policy of the parent struct.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `EnvStructError` if parsing fails.
+    fn parse_from_source_named(
+        source: &dyn EnvSource,
+        var_name: impl AsRef<str>,
+        default: Option<&str>,
+        naming: &EnvNaming,
+    ) -> Result<Self, EnvStructError>
     where
         Self: Sized;
 
@@ -61,13 +291,35 @@ pub trait EnvParseNested {
     fn get_env_entries(
         prefix: impl AsRef<str>,
         default: Option<&str>,
+    ) -> Result<Vec<EnvEntry>, EnvStructError> {
+        Self::get_env_entries_named(prefix, default, &EnvNaming::default())
+    }
+
+    /// Retrieves the environment entries, naming them with the policy inherited from the parent
+    /// struct unless the type declares its own.
+    ///
+    /// # Arguments
+    ///
+    /// * `prefix` - A prefix for the environment variables.
+    /// * `default` - An optional default value.
+    /// * `naming` - The naming policy of the parent struct.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `EnvStructError` if retrieval fails.
+    fn get_env_entries_named(
+        prefix: impl AsRef<str>,
+        default: Option<&str>,
+        naming: &EnvNaming,
     ) -> Result<Vec<EnvEntry>, EnvStructError>;
 }
 
 impl<T: EnvParseNested> EnvParseNested for Option<T> {
-    fn parse_from_env_var(
+    fn parse_from_source_named(
+        source: &dyn EnvSource,
         var_name: impl AsRef<str>,
         default: Option<&str>,
+        naming: &EnvNaming,
     ) -> Result<Self, EnvStructError>
     where
         Self: Sized,
@@ -76,25 +328,34 @@ impl<T: EnvParseNested> EnvParseNested for Option<T> {
 
         // Defining any environment variable of optional type makes the field required
         // otherwise it is None.
-        if !T::get_env_entries(var_name, default)?
+        if !T::get_env_entries_named(var_name, default, naming)?
             .iter()
-            .any(|entry| std::env::var_os(&entry.name).is_some())
+            .any(|entry| entry.is_present(source))
         {
             return Ok(None);
         }
 
-        Ok(Some(T::parse_from_env_var(var_name, default)?))
+        Ok(Some(T::parse_from_source_named(
+            source, var_name, default, naming,
+        )?))
     }
 
-    fn get_env_entries(
+    fn get_env_entries_named(
         prefix: impl AsRef<str>,
         default: Option<&str>,
+        naming: &EnvNaming,
     ) -> Result<Vec<EnvEntry>, EnvStructError> {
-        T::get_env_entries(prefix, default)
+        T::get_env_entries_named(prefix, default, naming)
+    }
+
+    fn env_prefix() -> Option<&'static str> {
+        T::env_prefix()
     }
 }
 
-/// Concatenates two environment variable names with an underscore.
+/// Concatenates two environment variable names with the default naming policy.
+///
+/// The names are uppercased with the `env_uppercase` feature and separated by an underscore.
 ///
 /// # Arguments
 ///
@@ -105,29 +366,27 @@ impl<T: EnvParseNested> EnvParseNested for Option<T> {
 ///
 /// A concatenated string of the two environment variable names.
 pub fn concat_env_name(lhs: impl AsRef<str>, rhs: impl AsRef<str>) -> String {
-    let (lhs, rhs) = (lhs.as_ref().to_uppercase(), rhs.as_ref().to_uppercase());
-    #[cfg(feature = "env_uppercase")]
-    let (lhs, rhs) = (lhs.to_uppercase(), rhs.to_uppercase());
-    match (lhs.is_empty(), rhs.is_empty()) {
-        (false, true) => lhs.to_string(),
-        (true, false) => rhs.to_string(),
-        _ => format!("{lhs}_{rhs}"),
-    }
+    EnvNaming::default().join(lhs, rhs)
 }
 
 macro_rules! implement_nested_t {
     ($x:ty) => {
         paste! {
             impl<T: EnvParseNested> EnvParseNested for $x::<T> {
-                fn parse_from_env_var(var_name: impl AsRef<str>, default: Option<&str>) -> Result<Self, EnvStructError> {
-                    Ok(T::parse_from_env_var(var_name, default)?.into())
+                fn parse_from_source_named(source: &dyn EnvSource, var_name: impl AsRef<str>, default: Option<&str>, naming: &EnvNaming) -> Result<Self, EnvStructError> {
+                    Ok(T::parse_from_source_named(source, var_name, default, naming)?.into())
                 }
 
-                fn get_env_entries(
+                fn get_env_entries_named(
                     prefix: impl AsRef<str>,
                     default: Option<&str>,
+                    naming: &EnvNaming,
                 ) -> Result<Vec<EnvEntry>, EnvStructError> {
-                    T::get_env_entries(prefix, default)
+                    T::get_env_entries_named(prefix, default, naming)
+                }
+
+                fn env_prefix() -> Option<&'static str> {
+                    T::env_prefix()
                 }
             }
         }
@@ -0,0 +1,129 @@
+use crate::*;
+
+/// Checks that a variable is set if another field has a given value, for
+/// `#[env(required_if(field = "...", value = "..."))]`.
+///
+/// # Arguments
+///
+/// * `var_name` - The name of the required variable.
+/// * `is_set` - Whether the required variable is set.
+/// * `other_var_name` - The name of the variable of the other field.
+/// * `other_value` - The value of the other field, `None` if it is not set.
+/// * `value` - The value of the other field requiring the variable.
+///
+/// # Errors
+///
+/// Returns an `EnvStructError::RelationError` if the other field has the value and the variable
+/// is not set.
+pub fn check_required_if(
+    var_name: &str,
+    is_set: bool,
+    other_var_name: &str,
+    other_value: Option<String>,
+    value: &str,
+) -> Result<(), EnvStructError> {
+    match !is_set && other_value.as_deref() == Some(value) {
+        true => Err(EnvStructError::RelationError {
+            rule: "required_if".to_string(),
+            var_names: vec![var_name.to_string(), other_var_name.to_string()],
+            message: format!("`{var_name}` is required when `{other_var_name}` is `{value}`"),
+        }),
+        false => Ok(()),
+    }
+}
+
+/// Checks that two variables are not both set, for `#[env(conflicts_with = "...")]`.
+///
+/// # Arguments
+///
+/// * `var_name` - The name of the variable.
+/// * `is_set` - Whether the variable is set.
+/// * `other_var_name` - The name of the conflicting variable.
+/// * `other_is_set` - Whether the conflicting variable is set.
+///
+/// # Errors
+///
+/// Returns an `EnvStructError::RelationError` if both variables are set.
+pub fn check_conflicts_with(
+    var_name: &str,
+    is_set: bool,
+    other_var_name: &str,
+    other_is_set: bool,
+) -> Result<(), EnvStructError> {
+    match is_set && other_is_set {
+        true => Err(EnvStructError::RelationError {
+            rule: "conflicts_with".to_string(),
+            var_names: vec![var_name.to_string(), other_var_name.to_string()],
+            message: format!(
+                "`{var_name}` conflicts with `{other_var_name}`, set only one of them"
+            ),
+        }),
+        false => Ok(()),
+    }
+}
+
+/// Checks the members of a group, for `#[env(group = "...")]`. At most one member may be set,
+/// exactly one if a member is marked with `exactly_one`.
+///
+/// # Arguments
+///
+/// * `group` - The name of the group.
+/// * `members` - The names of the variables of the members and whether they are set.
+/// * `exactly_one` - Whether one member must be set.
+///
+/// # Errors
+///
+/// Returns an `EnvStructError::RelationError` naming the set members if several are set, or
+/// naming all members if none is set but one is required.
+pub fn check_group(
+    group: &str,
+    members: &[(String, bool)],
+    exactly_one: bool,
+) -> Result<(), EnvStructError> {
+    let set: Vec<_> = members
+        .iter()
+        .filter(|(_, is_set)| *is_set)
+        .map(|(name, _)| name.clone())
+        .collect();
+    let all: Vec<_> = members.iter().map(|(name, _)| name.clone()).collect();
+
+    let (var_names, message) = match (set.len(), exactly_one) {
+        (0, true) => {
+            let message = format!(
+                "Exactly one of {} of group `{group}` must be set, none is set",
+                fmt_names(&all)
+            );
+            (all, message)
+        }
+        (0 | 1, _) => return Ok(()),
+        (_, exactly_one) => {
+            let message = format!(
+                "{} one of {} of group `{group}` may be set, {} are set",
+                if exactly_one { "Exactly" } else { "At most" },
+                fmt_names(&all),
+                fmt_names(&set)
+            );
+            (set, message)
+        }
+    };
+    Err(EnvStructError::RelationError {
+        rule: format!("group = {group}"),
+        var_names,
+        message,
+    })
+}
+
+/// Describes a group in the usage, e.g. ``group `auth`: exactly one of `A`, `B` ``.
+///
+/// # Arguments
+///
+/// * `group` - The name of the group.
+/// * `members` - The names of the variables of the members.
+/// * `exactly_one` - Whether one member must be set.
+pub fn group_rule(group: &str, members: &[String], exactly_one: bool) -> String {
+    format!(
+        "group `{group}`: {} one of {}",
+        if exactly_one { "exactly" } else { "at most" },
+        fmt_names(members)
+    )
+}
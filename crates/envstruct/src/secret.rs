@@ -0,0 +1,93 @@
+use crate::*;
+use zeroize::Zeroize;
+
+/// The placeholder printed instead of secret values.
+pub const REDACTED: &str = "[REDACTED]";
+
+/// A wrapper for secret values such as passwords and tokens.
+///
+/// `Secret<T>` parses like `T`, but prints [`REDACTED`] in `Debug` and `Display`, never includes
+/// the value in parse errors nor its default in usage, and zeroes the inner value on drop.
+///
+/// ```
+/// use envstruct::prelude::*;
+///
+/// #[derive(EnvStruct, Debug)]
+/// pub struct Config {
+///     pub password: Secret<String>,
+/// }
+///
+/// let source = MapSource::new([("APP_PASSWORD", "s3cr3t")]);
+/// let config = Config::from_source(&source, "APP")?;
+/// assert_eq!(config.password.expose(), "s3cr3t");
+/// assert_eq!(format!("{config:?}"), "Config { password: [REDACTED] }");
+/// # Ok::<(), EnvStructError>(())
+/// ```
+#[derive(Clone, Default, PartialEq, Eq)]
+pub struct Secret<T: Zeroize>(T);
+
+impl<T: Zeroize> Secret<T> {
+    /// Wraps a secret value.
+    pub fn new(value: T) -> Self {
+        Self(value)
+    }
+
+    /// Returns a reference to the secret value.
+    pub fn expose(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T: Zeroize> From<T> for Secret<T> {
+    fn from(value: T) -> Self {
+        Self(value)
+    }
+}
+
+impl<T: Zeroize> std::fmt::Debug for Secret<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(REDACTED)
+    }
+}
+
+impl<T: Zeroize> std::fmt::Display for Secret<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(REDACTED)
+    }
+}
+
+impl<T: Zeroize> Drop for Secret<T> {
+    fn drop(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+impl<T: EnvParsePrimitive + Zeroize> EnvParsePrimitive for Secret<T> {
+    fn parse(val: &str) -> Result<Self, BoxError> {
+        Ok(Self(T::parse(val)?))
+    }
+
+    fn parse_from_source(
+        source: &dyn EnvSource,
+        var_name: impl AsRef<str>,
+        default: Option<&str>,
+    ) -> Result<Self, EnvStructError> {
+        T::parse_from_source(source, var_name, default)
+            .map(Self)
+            .map_err(EnvStructError::redact)
+    }
+
+    fn hint() -> Option<String> {
+        T::hint()
+    }
+
+    fn get_env_entries(
+        prefix: impl AsRef<str>,
+        default: Option<&str>,
+    ) -> Result<Vec<EnvEntry>, EnvStructError> {
+        Ok(vec![EnvEntry {
+            secret: true,
+            ..EnvEntry::new(prefix.as_ref(), std::any::type_name::<Self>(), default)
+        }])
+    }
+}
@@ -0,0 +1,134 @@
+use crate::*;
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+};
+
+/// A source that maps the files of a directory to variables, e.g. Docker secrets mounted to
+/// `/run/secrets` or systemd credentials exposed by `LoadCredential=` in
+/// `$CREDENTIALS_DIRECTORY`.
+///
+/// The name of a file is the suffix of the variable name after the optional prefix of the
+/// source. It is converted like a field name, so the file `db_password` supplies
+/// `MY_APP_DB_PASSWORD` for the prefix `MY_APP`. A single trailing newline is stripped from the
+/// content of a file. Hidden files and subdirectories are ignored.
+///
+/// ```no_run
+/// use envstruct::prelude::*;
+///
+/// #[derive(EnvStruct)]
+/// pub struct Config {
+///     pub db_host: String,
+///     pub db_password: String,
+/// }
+///
+/// let source = ProcessEnv.fallback(SecretsDir::load("/run/secrets")?.with_prefix("MY_APP"));
+/// let config = Config::from_source(&source, "MY_APP")?;
+/// # Ok::<(), EnvStructError>(())
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct SecretsDir {
+    prefix: String,
+    files: BTreeMap<String, PathBuf>,
+}
+
+impl SecretsDir {
+    /// Lists the files of a directory.
+    ///
+    /// The content of the files is read on lookup.
+    ///
+    /// # Arguments
+    ///
+    /// * `dir` - The path of the directory.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `EnvStructError` if the directory cannot be read.
+    pub fn load(dir: impl AsRef<Path>) -> Result<Self, EnvStructError> {
+        let dir = dir.as_ref();
+        let read_error = |e| EnvStructError::ReadFileError {
+            path: dir.display().to_string(),
+            source: e,
+        };
+
+        let mut files = BTreeMap::new();
+        for entry in std::fs::read_dir(dir).map_err(read_error)? {
+            let entry = entry.map_err(read_error)?;
+            let Ok(name) = entry.file_name().into_string() else {
+                continue;
+            };
+            if name.starts_with('.') || entry.path().is_dir() {
+                continue;
+            }
+            files.insert(name, entry.path());
+        }
+
+        Ok(Self {
+            prefix: String::new(),
+            files,
+        })
+    }
+
+    /// Lists the credentials passed by systemd in `$CREDENTIALS_DIRECTORY`.
+    ///
+    /// Returns an empty source if `$CREDENTIALS_DIRECTORY` is not set.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `EnvStructError` if the directory cannot be read.
+    pub fn credentials_directory() -> Result<Self, EnvStructError> {
+        match std::env::var_os("CREDENTIALS_DIRECTORY") {
+            Some(dir) => Self::load(dir),
+            None => Ok(Self::default()),
+        }
+    }
+
+    /// Sets the prefix prepended to the file names to form the variable names.
+    ///
+    /// # Arguments
+    ///
+    /// * `prefix` - A prefix for the variables.
+    pub fn with_prefix(mut self, prefix: impl AsRef<str>) -> Self {
+        self.prefix = prefix.as_ref().to_string();
+        self
+    }
+
+    /// Finds the file that supplies a variable.
+    fn file(&self, name: &str) -> Option<&PathBuf> {
+        self.files
+            .iter()
+            .find(|(file_name, _)| concat_env_name(&self.prefix, file_name) == name)
+            .map(|(_, path)| path)
+    }
+}
+
+impl EnvSource for SecretsDir {
+    fn var(&self, name: &str) -> Result<Option<String>, EnvStructError> {
+        let Some(path) = self.file(name) else {
+            return Ok(None);
+        };
+        let mut content =
+            std::fs::read_to_string(path).map_err(|e| EnvStructError::ReadFileError {
+                path: path.display().to_string(),
+                source: e,
+            })?;
+        strip_trailing_newline(&mut content);
+        Ok(Some(content))
+    }
+
+    fn var_names(&self, prefix: &str) -> Vec<String> {
+        self.files
+            .keys()
+            .map(|file_name| concat_env_name(&self.prefix, file_name))
+            .filter(|name| name.starts_with(prefix))
+            .collect()
+    }
+
+    fn contains(&self, name: &str) -> bool {
+        self.file(name).is_some()
+    }
+
+    fn origin(&self, name: &str) -> Option<String> {
+        self.file(name).map(|path| path.display().to_string())
+    }
+}
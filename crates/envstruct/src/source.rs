@@ -0,0 +1,321 @@
+use crate::*;
+use std::collections::{BTreeMap, HashMap};
+
+/// A source of variables that configurations are parsed from.
+///
+/// The process environment is the default source, see [`ProcessEnv`]. Implement this trait to
+/// parse configurations from anything else that maps names to values.
+pub trait EnvSource {
+    /// Looks up the value of a variable by its name.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the variable.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Some(value))` if the variable is present.
+    /// * `Ok(None)` if the variable is not present.
+    /// * `Err(EnvStructError)` if the variable is present but its value cannot be read.
+    fn var(&self, name: &str) -> Result<Option<String>, EnvStructError>;
+
+    /// Returns the names of all variables that start with the given prefix.
+    ///
+    /// # Arguments
+    ///
+    /// * `prefix` - The prefix of the variable names.
+    fn var_names(&self, prefix: &str) -> Vec<String>;
+
+    /// Checks whether a variable is present, regardless of whether its value can be read.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the variable.
+    fn contains(&self, name: &str) -> bool {
+        !matches!(self.var(name), Ok(None))
+    }
+
+    /// Describes where the value of a variable comes from, e.g. a file and line number.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the variable.
+    fn origin(&self, _name: &str) -> Option<String> {
+        None
+    }
+
+    /// Reports a warning, e.g. a deprecated variable that is set.
+    ///
+    /// The warning is logged with the `log` crate if the `log` feature is enabled.
+    ///
+    /// # Arguments
+    ///
+    /// * `warning` - The warning to report.
+    fn warn(&self, _warning: EnvWarning) {
+        #[cfg(feature = "log")]
+        log::warn!("{_warning}");
+    }
+
+    /// Layers this source over another one, which is used for variables missing in this source.
+    ///
+    /// # Arguments
+    ///
+    /// * `lower` - The source to fall back to.
+    fn fallback<S: EnvSource>(self, lower: S) -> Fallback<Self, S>
+    where
+        Self: Sized,
+    {
+        Fallback { upper: self, lower }
+    }
+}
+
+impl<S: EnvSource + ?Sized> EnvSource for &S {
+    fn var(&self, name: &str) -> Result<Option<String>, EnvStructError> {
+        (**self).var(name)
+    }
+
+    fn var_names(&self, prefix: &str) -> Vec<String> {
+        (**self).var_names(prefix)
+    }
+
+    fn contains(&self, name: &str) -> bool {
+        (**self).contains(name)
+    }
+
+    fn origin(&self, name: &str) -> Option<String> {
+        (**self).origin(name)
+    }
+
+    fn warn(&self, warning: EnvWarning) {
+        (**self).warn(warning)
+    }
+}
+
+/// The environment of the current process.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ProcessEnv;
+
+impl EnvSource for ProcessEnv {
+    fn var(&self, name: &str) -> Result<Option<String>, EnvStructError> {
+        match std::env::var(name) {
+            Ok(value) => Ok(Some(value)),
+            Err(std::env::VarError::NotPresent) => Ok(None),
+            Err(std::env::VarError::NotUnicode(_)) => {
+                Err(EnvStructError::InvalidVarFormat(name.to_string()))
+            }
+        }
+    }
+
+    fn var_names(&self, prefix: &str) -> Vec<String> {
+        std::env::vars_os()
+            .filter_map(|(name, _)| name.into_string().ok())
+            .filter(|name| name.starts_with(prefix))
+            .collect()
+    }
+
+    fn contains(&self, name: &str) -> bool {
+        std::env::var_os(name).is_some()
+    }
+}
+
+impl EnvSource for HashMap<String, String> {
+    fn var(&self, name: &str) -> Result<Option<String>, EnvStructError> {
+        Ok(self.get(name).cloned())
+    }
+
+    fn var_names(&self, prefix: &str) -> Vec<String> {
+        self.keys()
+            .filter(|name| name.starts_with(prefix))
+            .cloned()
+            .collect()
+    }
+}
+
+impl EnvSource for BTreeMap<String, String> {
+    fn var(&self, name: &str) -> Result<Option<String>, EnvStructError> {
+        Ok(self.get(name).cloned())
+    }
+
+    fn var_names(&self, prefix: &str) -> Vec<String> {
+        self.range(prefix.to_string()..)
+            .map(|(name, _)| name)
+            .take_while(|name| name.starts_with(prefix))
+            .cloned()
+            .collect()
+    }
+}
+
+/// An in-memory source built from any collection of name-value pairs.
+///
+/// ```
+/// use envstruct::MapSource;
+///
+/// let source = MapSource::new([("APP_HOST", "localhost"), ("APP_PORT", "8080")]);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct MapSource(pub BTreeMap<String, String>);
+
+impl MapSource {
+    /// Creates a new source from name-value pairs.
+    ///
+    /// # Arguments
+    ///
+    /// * `vars` - The name-value pairs. Later pairs override earlier pairs with the same name.
+    pub fn new<K, V>(vars: impl IntoIterator<Item = (K, V)>) -> Self
+    where
+        K: Into<String>,
+        V: Into<String>,
+    {
+        vars.into_iter().collect()
+    }
+}
+
+impl<K, V> FromIterator<(K, V)> for MapSource
+where
+    K: Into<String>,
+    V: Into<String>,
+{
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        Self(
+            iter.into_iter()
+                .map(|(name, value)| (name.into(), value.into()))
+                .collect(),
+        )
+    }
+}
+
+impl EnvSource for MapSource {
+    fn var(&self, name: &str) -> Result<Option<String>, EnvStructError> {
+        self.0.var(name)
+    }
+
+    fn var_names(&self, prefix: &str) -> Vec<String> {
+        self.0.var_names(prefix)
+    }
+}
+
+/// A source that looks variables up in the upper source first and falls back to the lower one.
+///
+/// Created by [`EnvSource::fallback`].
+#[derive(Debug, Clone, Default)]
+pub struct Fallback<U, L> {
+    upper: U,
+    lower: L,
+}
+
+impl<U: EnvSource, L: EnvSource> EnvSource for Fallback<U, L> {
+    fn var(&self, name: &str) -> Result<Option<String>, EnvStructError> {
+        match self.upper.var(name)? {
+            Some(value) => Ok(Some(value)),
+            None => self.lower.var(name),
+        }
+    }
+
+    fn var_names(&self, prefix: &str) -> Vec<String> {
+        let mut names = self.upper.var_names(prefix);
+        names.extend(self.lower.var_names(prefix));
+        names.sort();
+        names.dedup();
+        names
+    }
+
+    fn contains(&self, name: &str) -> bool {
+        self.upper.contains(name) || self.lower.contains(name)
+    }
+
+    fn origin(&self, name: &str) -> Option<String> {
+        if self.upper.contains(name) {
+            self.upper.origin(name)
+        } else {
+            self.lower.origin(name)
+        }
+    }
+
+    fn warn(&self, warning: EnvWarning) {
+        self.upper.warn(warning)
+    }
+}
+
+/// A source that reads a variable from a file when the variable itself is absent but
+/// `<NAME>_FILE` holds the path of a file, as used for Docker and Kubernetes secrets.
+///
+/// A single trailing newline is stripped from the content of the file.
+///
+/// ```no_run
+/// use envstruct::prelude::*;
+///
+/// #[derive(EnvStruct)]
+/// pub struct Config {
+///     pub db_password: String,
+/// }
+///
+/// // MY_APP_DB_PASSWORD_FILE=/run/secrets/db_password
+/// let config = Config::from_source(&FileFallback(ProcessEnv), "MY_APP")?;
+/// # Ok::<(), EnvStructError>(())
+/// ```
+///
+/// The same behaviour can be enabled for single fields or whole structs with
+/// `#[env(file_fallback)]`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FileFallback<S>(pub S);
+
+impl<S: EnvSource> FileFallback<S> {
+    /// Returns the name and value of the `<NAME>_FILE` variable if `name` itself is absent.
+    fn file_var(&self, name: &str) -> Result<Option<(String, String)>, EnvStructError> {
+        if self.0.contains(name) {
+            return Ok(None);
+        }
+        let file_var_name = format!("{name}_FILE");
+        Ok(self
+            .0
+            .var(&file_var_name)?
+            .map(|path| (file_var_name, path)))
+    }
+}
+
+impl<S: EnvSource> EnvSource for FileFallback<S> {
+    fn var(&self, name: &str) -> Result<Option<String>, EnvStructError> {
+        let Some((file_var_name, path)) = self.file_var(name)? else {
+            return self.0.var(name);
+        };
+        let mut content =
+            std::fs::read_to_string(&path).map_err(|e| EnvStructError::ReadVarFileError {
+                var_name: name.to_string(),
+                file_var_name,
+                path,
+                field: Box::default(),
+                source: e,
+            })?;
+        strip_trailing_newline(&mut content);
+        Ok(Some(content))
+    }
+
+    fn var_names(&self, prefix: &str) -> Vec<String> {
+        self.0.var_names(prefix)
+    }
+
+    fn contains(&self, name: &str) -> bool {
+        self.0.contains(name) || self.0.contains(&format!("{name}_FILE"))
+    }
+
+    fn origin(&self, name: &str) -> Option<String> {
+        match self.file_var(name) {
+            Ok(Some((file_var_name, path))) => Some(format!("`{path}` named by `{file_var_name}`")),
+            _ => self.0.origin(name),
+        }
+    }
+
+    fn warn(&self, warning: EnvWarning) {
+        self.0.warn(warning)
+    }
+}
+
+/// Strips a single trailing `\n` or `\r\n` from the content of a file.
+pub(crate) fn strip_trailing_newline(content: &mut String) {
+    if content.ends_with('\n') {
+        content.pop();
+        if content.ends_with('\r') {
+            content.pop();
+        }
+    }
+}
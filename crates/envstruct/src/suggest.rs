@@ -0,0 +1,103 @@
+use crate::*;
+
+/// Attaches "did you mean" suggestions to the missing variable errors.
+///
+/// Candidates are the variables of the source that do not belong to any entry of the
+/// configuration, so a present variable of another field is never suggested.
+///
+/// # Arguments
+///
+/// * `errors` - The errors of parsing the configuration.
+/// * `source` - The source the configuration is parsed from.
+/// * `entries` - The entries of the configuration.
+pub(crate) fn add_suggestions(
+    errors: EnvStructErrors,
+    source: &dyn EnvSource,
+    entries: &[EnvEntry],
+) -> EnvStructErrors {
+    let candidates: Vec<String> = source
+        .var_names("")
+        .into_iter()
+        .filter(|name| !entries.iter().any(|entry| entry.matches(name)))
+        .collect();
+
+    let mut result = EnvStructErrors::new();
+    for err in errors {
+        result.push(match err {
+            EnvStructError::MissingEnvVar {
+                var_name,
+                suggestion: None,
+                field,
+            } => EnvStructError::MissingEnvVar {
+                suggestion: suggest_var_name(&var_name, &candidates),
+                var_name,
+                field,
+            },
+            err => err,
+        });
+    }
+    result
+}
+
+/// Finds the candidate closest to a variable name within a small edit distance.
+///
+/// Names differing only in case and separators, e.g. `MYAPP_DB_HOST` and `my_app_db_host`, are
+/// preferred over misspellings like `MY_APP_DB_HSOT`.
+///
+/// # Arguments
+///
+/// * `name` - The name of the missing variable.
+/// * `candidates` - The names of the present variables.
+pub(crate) fn suggest_var_name(name: &str, candidates: &[String]) -> Option<String> {
+    let normalize = |name: &str| -> String {
+        name.chars()
+            .filter(|c| !matches!(c, '_' | '-' | '.'))
+            .flat_map(char::to_uppercase)
+            .collect()
+    };
+    let normalized = normalize(name);
+    let max_distance = (name.chars().count() / 5).clamp(1, 3);
+
+    candidates
+        .iter()
+        .filter_map(|candidate| {
+            let distance = match normalize(candidate) == normalized {
+                true => 0,
+                false => strsim::osa_distance(&name.to_uppercase(), &candidate.to_uppercase()),
+            };
+            (distance <= max_distance).then_some((distance, candidate))
+        })
+        .min()
+        .map(|(_, candidate)| candidate.to_string())
+}
+
+#[test]
+fn test_suggest_var_name() {
+    let candidates: Vec<String> = [
+        "MY_APP_DB_HSOT",
+        "MYAPP_DB_PORT",
+        "my_app_db_user",
+        "MY_APP_CACHE_HOST",
+        "PATH",
+    ]
+    .into_iter()
+    .map(String::from)
+    .collect();
+
+    let names = vec![
+        ("MY_APP_DB_HOST", Some("MY_APP_DB_HSOT")),
+        ("MY_APP_DB_PORT", Some("MYAPP_DB_PORT")),
+        ("MY_APP_DB_USER", Some("my_app_db_user")),
+        ("MY_APP_DB_NAME", None),
+        ("PAT", Some("PATH")),
+        ("HOME", None),
+    ];
+
+    for (name, expected) in names {
+        assert_eq!(
+            suggest_var_name(name, &candidates).as_deref(),
+            expected,
+            "{name}"
+        );
+    }
+}
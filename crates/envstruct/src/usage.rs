@@ -2,17 +2,216 @@ use crate::*;
 use prettytable::{format, Cell, Row, Table};
 
 /// Represents an environment variable entry with its name, type, and optional default value.
+///
+/// `secret` marks entries whose values and defaults must not be printed.
+/// `description` is taken from the doc comment or the `help` attribute of the field.
+/// `aliases` are the alternative names of the variable, tried in order if it is absent.
+/// `deprecated` is the message of the `deprecated` attribute, it applies to the aliases if there
+/// are any, to the variable itself otherwise.
+/// `file_fallback` marks entries also read from the file named by `<NAME>_FILE`.
+///
+/// New fields may be added, create entries with [`EnvEntry::new`].
+#[derive(Debug, Clone)]
+#[non_exhaustive]
 pub struct EnvEntry {
     pub name: String,
     pub typ: String,
     pub default: Option<String>,
+    pub secret: bool,
+    pub description: Option<String>,
+    pub aliases: Vec<String>,
+    pub deprecated: Option<String>,
+    pub constraints: Vec<String>,
+    pub file_fallback: bool,
+}
+
+impl EnvEntry {
+    /// Creates an entry of a variable that is neither secret nor documented.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the variable.
+    /// * `typ` - The fully qualified type name, e.g. `std::any::type_name::<T>()`.
+    /// * `default` - An optional default value.
+    pub fn new(name: &str, typ: &str, default: Option<&str>) -> Self {
+        Self {
+            name: name.to_string(),
+            typ: typ.to_string(),
+            default: default.map(ToString::to_string),
+            secret: false,
+            description: None,
+            aliases: Vec::new(),
+            deprecated: None,
+            constraints: Vec::new(),
+            file_fallback: false,
+        }
+    }
+
+    /// Returns the name of the variable followed by its aliases.
+    pub fn names(&self) -> impl Iterator<Item = &String> {
+        std::iter::once(&self.name).chain(&self.aliases)
+    }
+
+    /// Returns whether a variable is described by the entry: it is the name or an alias, or it
+    /// matches the name as a pattern where [`INDEX_PLACEHOLDER`] stands for an index,
+    /// [`KEY_PLACEHOLDER`] for a key and a trailing `*` for any suffix.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the variable.
+    pub fn matches(&self, name: &str) -> bool {
+        self.names().any(|pattern| matches_pattern(pattern, name))
+    }
+
+    /// Returns whether any variable described by the entry, an alias included, is present in the
+    /// source.
+    pub(crate) fn is_present(&self, source: &dyn EnvSource) -> bool {
+        match self.expand(source) {
+            Some(names) => !names.is_empty(),
+            None => {
+                let source = self.source(source);
+                self.names().any(|name| source.contains(name))
+            }
+        }
+    }
+
+    /// Returns the sorted names of the variables of the source matching the entry if its name
+    /// is a pattern, e.g. `APP_SERVERS_<N>_HOST`, `None` if it names a single variable.
+    ///
+    /// A `<NAME>_FILE` variable stands for `<NAME>` if the entry has a file fallback.
+    pub(crate) fn expand(&self, source: &dyn EnvSource) -> Option<Vec<String>> {
+        let end = self.name.find(['*', '<'])?;
+        let mut names: Vec<_> = source
+            .var_names(&self.name[..end])
+            .into_iter()
+            .map(|name| match name.strip_suffix("_FILE") {
+                Some(stripped) if self.file_fallback && !self.matches(&name) => {
+                    stripped.to_string()
+                }
+                _ => name,
+            })
+            .filter(|name| self.matches(name))
+            .collect();
+        names.sort();
+        names.dedup();
+        Some(names)
+    }
+
+    /// Returns the source the values of the entry are read from, wrapped in [`FileFallback`] if
+    /// the entry has a file fallback.
+    pub(crate) fn source<'a>(&self, source: &'a dyn EnvSource) -> Box<dyn EnvSource + 'a> {
+        match self.file_fallback {
+            true => Box::new(FileFallback(source)),
+            false => Box::new(source),
+        }
+    }
+
+    /// Returns the description including the deprecation message and the constraints, if any.
+    fn full_description(&self) -> Option<String> {
+        let deprecated = self
+            .deprecated
+            .as_ref()
+            .map(|message| format!("Deprecated: {message}"));
+        let constraints = (!self.constraints.is_empty())
+            .then(|| format!("Constraints: {}", self.constraints.join("; ")));
+        let lines: Vec<_> = [self.description.clone(), deprecated, constraints]
+            .into_iter()
+            .flatten()
+            .collect();
+        (!lines.is_empty()).then(|| lines.join("\n"))
+    }
+
+    /// Returns the names listed in the usage, flagging aliases and deprecated names.
+    fn usage_names(&self) -> String {
+        let flag = |alias: bool| match (alias, &self.deprecated, self.aliases.is_empty()) {
+            (true, Some(_), _) => " (deprecated alias)",
+            (true, None, _) => " (alias)",
+            (false, Some(_), true) => " (deprecated)",
+            (false, _, _) => "",
+        };
+        self.names()
+            .enumerate()
+            .map(|(i, name)| format!("{name}{}", flag(i > 0)))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+/// Stands for the index of an element in the names of the entries of a list, e.g.
+/// `APP_SERVERS_<N>_HOST`.
+pub const INDEX_PLACEHOLDER: &str = "<N>";
+
+/// Stands for the key of an element in the names of the entries of a map, e.g.
+/// `APP_DB_<NAME>_HOST`.
+pub const KEY_PLACEHOLDER: &str = "<NAME>";
+
+/// Finds the first placeholder of a pattern, returning its position and the placeholder.
+fn find_placeholder(pattern: &str) -> Option<(usize, &'static str)> {
+    // the placeholders are lowercased in kebab-case names
+    let upper = pattern.to_ascii_uppercase();
+    [INDEX_PLACEHOLDER, KEY_PLACEHOLDER]
+        .into_iter()
+        .filter_map(|placeholder| Some((upper.find(placeholder)?, placeholder)))
+        .min()
+}
+
+/// Matches a name against a pattern of an entry, see [`EnvEntry::matches`].
+fn matches_pattern(pattern: &str, name: &str) -> bool {
+    match find_placeholder(pattern) {
+        Some((start, INDEX_PLACEHOLDER)) => {
+            let Some(rest) = name.strip_prefix(&pattern[..start]) else {
+                return false;
+            };
+            let digits = rest.len() - rest.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+            digits > 0
+                && matches_pattern(&pattern[start + INDEX_PLACEHOLDER.len()..], &rest[digits..])
+        }
+        Some((start, placeholder)) => match_placeholder(
+            &pattern[..start],
+            &pattern[start + placeholder.len()..],
+            name,
+        )
+        .is_some(),
+        None => match pattern.strip_suffix('*') {
+            Some(prefix) => name.starts_with(prefix),
+            None => pattern == name,
+        },
+    }
+}
+
+/// Finds the shortest non-empty part of a name between `before` and a rest matching `after`.
+fn match_placeholder<'a>(before: &str, after: &str, name: &'a str) -> Option<&'a str> {
+    let rest = name.strip_prefix(before)?;
+    (1..=rest.len())
+        .filter(|end| rest.is_char_boundary(*end))
+        .find(|end| matches_pattern(after, &rest[*end..]))
+        .map(|end| &rest[..end])
+}
+
+/// Extracts the key standing for the [`KEY_PLACEHOLDER`] of a pattern from a name, e.g. `EU_1`
+/// from `APP_DB_EU_1_HOST` for `APP_DB_<NAME>_HOST`.
+///
+/// # Arguments
+///
+/// * `pattern` - The name of an entry.
+/// * `name` - The name of a variable.
+pub(crate) fn match_key<'a>(pattern: &str, name: &'a str) -> Option<&'a str> {
+    match find_placeholder(pattern)? {
+        (start, KEY_PLACEHOLDER) => match_placeholder(
+            &pattern[..start],
+            &pattern[start + KEY_PLACEHOLDER.len()..],
+            name,
+        ),
+        _ => None,
+    }
 }
 
 /// A trait for generating usage information for environment variables.
 pub trait EnvStructUsage: EnvParseNested {
-    /// Generates a usage table for environment variables without any prefix.
+    /// Generates a usage table for environment variables under the prefix declared with
+    /// `#[env(prefix = "...")]`, if any.
     fn usage() -> Result<String, EnvStructError> {
-        Self::usage_with_prefix("")
+        Self::usage_with_prefix(Self::env_prefix().unwrap_or_default())
     }
 
     /// Generates a usage table for environment variables with the given prefix.
@@ -21,25 +220,46 @@ pub trait EnvStructUsage: EnvParseNested {
     ///
     /// * `prefix` - A string slice that holds the prefix to be used for the environment variables.
     fn usage_with_prefix(prefix: impl AsRef<str>) -> Result<String, EnvStructError> {
+        let entries = Self::get_env_entries(prefix, None)?;
+        // the description column is only rendered if any entry is documented
+        let described = entries
+            .iter()
+            .any(|entry| entry.full_description().is_some());
+
         let mut table = Table::new();
-        table.set_titles(Row::new(vec![
+        let mut titles = Row::new(vec![
             Cell::new("NAME"),
             Cell::new("TYPE"),
             Cell::new("DEFAULT"),
-        ]));
+        ]);
+        if described {
+            titles.add_cell(Cell::new("DESCRIPTION"));
+        }
+        table.set_titles(titles);
 
-        for entry in Self::get_env_entries(prefix, None)? {
-            table.add_row(Row::new(vec![
-                Cell::new(&entry.name),
+        for entry in entries {
+            let mut row = Row::new(vec![
+                Cell::new(&entry.usage_names()),
                 Cell::new(&strip_namespace(&entry.typ)),
                 Cell::new(
                     // quote all default values to distinct it from empty strings
                     &entry
                         .default
-                        .map(|v| format!(r#""{v}""#))
+                        .as_ref()
+                        .map(|v| match entry.secret {
+                            true => REDACTED.to_string(),
+                            false => format!(r#""{v}""#),
+                        })
                         .unwrap_or_default(),
                 ),
-            ]));
+            ]);
+            if described {
+                row.add_cell(Cell::new(&wrap_text(
+                    entry.full_description().as_deref().unwrap_or_default(),
+                    DESCRIPTION_WIDTH,
+                )));
+            }
+            table.add_row(row);
         }
 
         table.set_format(*format::consts::FORMAT_NO_BORDER_LINE_SEPARATOR);
@@ -50,6 +270,35 @@ pub trait EnvStructUsage: EnvParseNested {
 
 impl<T: EnvParseNested> EnvStructUsage for T {}
 
+/// The maximum width of the description column in usage tables.
+const DESCRIPTION_WIDTH: usize = 60;
+
+/// Wraps a text into lines of at most `width` characters, breaking at whitespace.
+///
+/// Words longer than `width` are kept on their own line.
+///
+/// # Arguments
+///
+/// * `text` - A string slice that holds the text to wrap.
+/// * `width` - The maximum width of a line.
+fn wrap_text(text: &str, width: usize) -> String {
+    let mut lines: Vec<String> = Vec::new();
+    for paragraph in text.lines() {
+        let mut line = String::new();
+        for word in paragraph.split_whitespace() {
+            if !line.is_empty() && line.chars().count() + 1 + word.chars().count() > width {
+                lines.push(std::mem::take(&mut line));
+            }
+            if !line.is_empty() {
+                line.push(' ');
+            }
+            line.push_str(word);
+        }
+        lines.push(line);
+    }
+    lines.join("\n")
+}
+
 /// Strips the namespace from a type name, leaving only the base type.
 ///
 /// # Arguments
@@ -59,7 +308,7 @@ impl<T: EnvParseNested> EnvStructUsage for T {}
 /// # Returns
 ///
 /// A `String` containing the base type name without the namespace.
-fn strip_namespace(name: &str) -> String {
+pub(crate) fn strip_namespace(name: &str) -> String {
     // Off the top of my head
     static SPLITTERS: &[char] = &[
         '(', ')', '[', ']', '<', '>', '{', '}',
@@ -103,3 +352,18 @@ fn test_strip_namespace() {
         assert_eq!(strip_namespace(typ), expected);
     }
 }
+
+#[test]
+fn test_wrap_text() {
+    let texts = vec![
+        ("", 10, ""),
+        ("short", 10, "short"),
+        ("the quick brown fox", 10, "the quick\nbrown fox"),
+        ("a verylongword here", 5, "a\nverylongword\nhere"),
+        ("first paragraph\nsecond", 20, "first paragraph\nsecond"),
+    ];
+
+    for (text, width, expected) in texts {
+        assert_eq!(wrap_text(text, width), expected);
+    }
+}
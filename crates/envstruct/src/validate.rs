@@ -0,0 +1,208 @@
+use crate::*;
+use std::fmt::Display;
+
+/// Checks that a value is at least `min`, for `#[env(min = ...)]`.
+///
+/// # Arguments
+///
+/// * `var_name` - The name of the variable the value is read from.
+/// * `value` - The parsed value.
+/// * `min` - The smallest valid value.
+///
+/// # Errors
+///
+/// Returns an `EnvStructError::ValidationError` if the value is smaller than `min`.
+pub fn check_min<T: PartialOrd + Display>(
+    var_name: &str,
+    value: &T,
+    min: T,
+) -> Result<(), EnvStructError> {
+    match *value < min {
+        true => Err(validation_error(
+            var_name,
+            format!("min = {min}"),
+            format!("must be at least {min}"),
+        )),
+        false => Ok(()),
+    }
+}
+
+/// Checks that a value is at most `max`, for `#[env(max = ...)]`.
+///
+/// # Arguments
+///
+/// * `var_name` - The name of the variable the value is read from.
+/// * `value` - The parsed value.
+/// * `max` - The largest valid value.
+///
+/// # Errors
+///
+/// Returns an `EnvStructError::ValidationError` if the value is larger than `max`.
+pub fn check_max<T: PartialOrd + Display>(
+    var_name: &str,
+    value: &T,
+    max: T,
+) -> Result<(), EnvStructError> {
+    match *value > max {
+        true => Err(validation_error(
+            var_name,
+            format!("max = {max}"),
+            format!("must be at most {max}"),
+        )),
+        false => Ok(()),
+    }
+}
+
+/// Checks the length of a value, for `#[env(len(min = ..., max = ...))]`.
+///
+/// # Arguments
+///
+/// * `var_name` - The name of the variable the value is read from.
+/// * `len` - The length of the parsed value, e.g. the number of characters or items.
+/// * `min` - The smallest valid length.
+/// * `max` - The largest valid length.
+///
+/// # Errors
+///
+/// Returns an `EnvStructError::ValidationError` if the length is out of bounds.
+pub fn check_len(
+    var_name: &str,
+    len: usize,
+    min: Option<usize>,
+    max: Option<usize>,
+) -> Result<(), EnvStructError> {
+    if min.is_some_and(|min| len < min) || max.is_some_and(|max| len > max) {
+        let rule = len_rule(min, max);
+        return Err(validation_error(
+            var_name,
+            rule.clone(),
+            format!("length {len} must be {}", rule.trim_start_matches("len ")),
+        ));
+    }
+    Ok(())
+}
+
+/// Describes the bounds of a length, e.g. `len 1..=64`.
+///
+/// # Arguments
+///
+/// * `min` - The smallest valid length.
+/// * `max` - The largest valid length.
+pub fn len_rule(min: Option<usize>, max: Option<usize>) -> String {
+    match (min, max) {
+        (Some(min), Some(max)) => format!("len {min}..={max}"),
+        (Some(min), None) => format!("len >= {min}"),
+        (None, Some(max)) => format!("len <= {max}"),
+        (None, None) => "len".to_string(),
+    }
+}
+
+/// Checks that a value is one of the accepted values, for `#[env(one_of = [...])]`.
+///
+/// The value is compared by its `Display` representation.
+///
+/// # Arguments
+///
+/// * `var_name` - The name of the variable the value is read from.
+/// * `value` - The parsed value.
+/// * `accepted` - The accepted values.
+///
+/// # Errors
+///
+/// Returns an `EnvStructError::ValidationError` if the value is not accepted.
+pub fn check_one_of<T: Display>(
+    var_name: &str,
+    value: &T,
+    accepted: &[&str],
+) -> Result<(), EnvStructError> {
+    let value = value.to_string();
+    match accepted.contains(&value.as_str()) {
+        true => Ok(()),
+        false => Err(validation_error(
+            var_name,
+            format!("one of: {}", accepted.join(", ")),
+            format!("must be one of: {}", accepted.join(", ")),
+        )),
+    }
+}
+
+/// Checks that a value matches a regular expression, for `#[env(regex = "...")]`.
+///
+/// The value is matched by its `Display` representation.
+///
+/// # Arguments
+///
+/// * `var_name` - The name of the variable the value is read from.
+/// * `value` - The parsed value.
+/// * `pattern` - The regular expression.
+///
+/// # Errors
+///
+/// Returns an `EnvStructError::ValidationError` if the value does not match or the pattern is
+/// invalid.
+#[cfg(feature = "regex")]
+pub fn check_regex<T: Display>(
+    var_name: &str,
+    value: &T,
+    pattern: &str,
+) -> Result<(), EnvStructError> {
+    let rule = format!("regex = {pattern}");
+    let regex = regex::Regex::new(pattern)
+        .map_err(|e| validation_error(var_name, rule.clone(), format!("invalid pattern, {e}")))?;
+    match regex.is_match(&value.to_string()) {
+        true => Ok(()),
+        false => Err(validation_error(
+            var_name,
+            rule,
+            format!("must match `{pattern}`"),
+        )),
+    }
+}
+
+/// Runs the checks of a field on its parsed value, the errors name the type of the value as the
+/// expected type.
+///
+/// # Arguments
+///
+/// * `value` - The parsed value.
+/// * `checks` - The checks of the value, e.g. `check_min`.
+///
+/// # Errors
+///
+/// Returns the `EnvStructError::ValidationError` of the first failing check.
+pub fn check_value<T: ?Sized>(
+    value: &T,
+    checks: impl FnOnce(&T) -> Result<(), EnvStructError>,
+) -> Result<(), EnvStructError> {
+    checks(value).map_err(|err| err.with_type(&FieldInfo::new(std::any::type_name::<T>(), None)))
+}
+
+/// Converts the result of a custom validation function, for `#[env(validate = ...)]` and
+/// `#[env(validate_with = ...)]`.
+///
+/// # Arguments
+///
+/// * `var_name` - The name of the variable the value is read from, the prefix of a struct.
+/// * `rule` - The description of the rule, e.g. `validate = check_port`.
+/// * `result` - The result of the validation function.
+///
+/// # Errors
+///
+/// Returns an `EnvStructError::ValidationError` with the message of the error if validation
+/// failed.
+pub fn check_with<E: Display>(
+    var_name: &str,
+    rule: &str,
+    result: Result<(), E>,
+) -> Result<(), EnvStructError> {
+    result.map_err(|e| validation_error(var_name, rule.to_string(), e.to_string()))
+}
+
+fn validation_error(var_name: &str, rule: String, message: String) -> EnvStructError {
+    EnvStructError::ValidationError {
+        var_name: var_name.to_string(),
+        rule,
+        message,
+        field: Box::default(),
+    }
+}
@@ -0,0 +1,160 @@
+use crate::*;
+
+/// The accepted values of an enum deriving `EnvStruct`, selecting its variants.
+#[derive(Debug, Clone, Copy)]
+pub struct EnvVariants<'a> {
+    /// The names of each variant, the first one is listed in usage and errors, the others are
+    /// aliases.
+    pub names: &'a [&'a [&'a str]],
+    /// Whether the names are matched respecting the case.
+    pub case_sensitive: bool,
+}
+
+impl EnvVariants<'_> {
+    /// Finds the variant named by a value.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The value naming a variant.
+    ///
+    /// # Returns
+    ///
+    /// The index of the variant.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no variant has the name, the accepted values are listed by
+    /// [`EnvVariants::hint`].
+    pub fn find(&self, value: &str) -> Result<usize, BoxError> {
+        let value = value.trim();
+        self.names
+            .iter()
+            .position(|names| {
+                names.iter().any(|name| match self.case_sensitive {
+                    true => *name == value,
+                    false => name.eq_ignore_ascii_case(value),
+                })
+            })
+            .ok_or_else(|| format!("unknown variant `{value}`").into())
+    }
+
+    /// Describes the accepted values, e.g. `one of: local, remote, mock`.
+    pub fn hint(&self) -> String {
+        let names: Vec<_> = self
+            .names
+            .iter()
+            .filter_map(|names| names.first())
+            .copied()
+            .collect();
+        format!("one of: {}", names.join(", "))
+    }
+
+    /// Describes the variable of the enum in the usage.
+    ///
+    /// # Arguments
+    ///
+    /// * `var_name` - The name of the variable.
+    /// * `typ` - The name of the enum.
+    /// * `default` - The default value of the variable.
+    pub fn entry(&self, var_name: &str, typ: &str, default: Option<&str>) -> EnvEntry {
+        EnvEntry {
+            constraints: vec![self.hint()],
+            ..EnvEntry::new(var_name, typ, default)
+        }
+    }
+}
+
+/// Parses the tag variable selecting the variant of an enum deriving `EnvStruct`.
+///
+/// # Arguments
+///
+/// * `source` - The source to look the variable up in.
+/// * `var_name` - The name of the tag variable.
+/// * `default` - The variant selected if the variable is absent.
+/// * `typ` - The name of the enum.
+/// * `variants` - The accepted values of the tag.
+///
+/// # Returns
+///
+/// The index of the selected variant.
+///
+/// # Errors
+///
+/// Returns an `EnvStructError` if the tag variable is missing or names no variant.
+pub fn parse_tag(
+    source: &dyn EnvSource,
+    var_name: &str,
+    default: Option<&str>,
+    typ: &str,
+    variants: &EnvVariants,
+) -> Result<usize, EnvStructError> {
+    let field = || Box::new(FieldInfo::new(typ, Some(variants.hint())));
+    match source.var(var_name)? {
+        Some(value) => variants
+            .find(&value)
+            .map_err(|e| EnvStructError::ParseEnvError {
+                var_name: var_name.to_string(),
+                var_value: value,
+                origin: source.origin(var_name),
+                field: field(),
+                source: e,
+            }),
+        None => match default {
+            Some(default) => {
+                variants
+                    .find(default)
+                    .map_err(|e| EnvStructError::ParseDefaultError {
+                        var_name: var_name.to_string(),
+                        var_value: default.to_string(),
+                        field: field(),
+                        source: e,
+                    })
+            }
+            None => Err(EnvStructError::MissingEnvVar {
+                var_name: var_name.to_string(),
+                suggestion: None,
+                field: field(),
+            }),
+        },
+    }
+}
+
+/// Describes the tag variable of an enum deriving `EnvStruct` in the usage.
+///
+/// # Arguments
+///
+/// * `var_name` - The name of the tag variable.
+/// * `typ` - The name of the enum.
+/// * `default` - The variant selected if the variable is absent.
+/// * `variants` - The accepted values of the tag.
+pub fn tag_entry(
+    var_name: &str,
+    typ: &str,
+    default: Option<&str>,
+    variants: &EnvVariants,
+) -> EnvEntry {
+    EnvEntry {
+        description: Some("Selects the variant".to_string()),
+        ..variants.entry(var_name, typ, default)
+    }
+}
+
+/// Marks the entries of a variant of an enum deriving `EnvStruct` as used only if the tag
+/// selects the variant.
+///
+/// # Arguments
+///
+/// * `entries` - The entries of the fields of the variant.
+/// * `tag_name` - The name of the tag variable.
+/// * `variant` - The name of the variant.
+pub fn variant_entries(entries: Vec<EnvEntry>, tag_name: &str, variant: &str) -> Vec<EnvEntry> {
+    entries
+        .into_iter()
+        .map(|mut entry| {
+            entry
+                .constraints
+                .insert(0, format!("used if `{tag_name}` is `{variant}`"));
+            entry
+        })
+        .collect()
+}
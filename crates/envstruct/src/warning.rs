@@ -0,0 +1,116 @@
+use crate::*;
+use std::cell::RefCell;
+
+/// A problem with the variables that does not prevent parsing a configuration.
+///
+/// Warnings are reported to the source, see [`EnvSource::warn`], and returned by
+/// [`EnvParseNested::from_source_with_warnings`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EnvWarning {
+    /// A variable marked with `#[env(deprecated = "...")]` is set.
+    ///
+    /// `var_name` is the name of the deprecated variable.
+    /// `replacement` is the name of the variable to use instead if the deprecated one is an alias.
+    /// `message` is the message of the attribute, e.g. `use DB_URL`.
+    DeprecatedVar {
+        var_name: String,
+        replacement: Option<String>,
+        message: String,
+    },
+}
+
+impl std::fmt::Display for EnvWarning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EnvWarning::DeprecatedVar {
+                var_name,
+                replacement,
+                message,
+            } => {
+                write!(f, "Environment variable `{var_name}` is deprecated")?;
+                if let Some(replacement) = replacement {
+                    write!(f, " in favor of `{replacement}`")?;
+                }
+                if !message.is_empty() {
+                    write!(f, ", {message}")?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// A source that records the warnings reported while parsing from the inner source.
+pub(crate) struct WarningCollector<'a> {
+    pub(crate) source: &'a dyn EnvSource,
+    pub(crate) warnings: RefCell<Vec<EnvWarning>>,
+}
+
+impl EnvSource for WarningCollector<'_> {
+    fn var(&self, name: &str) -> Result<Option<String>, EnvStructError> {
+        self.source.var(name)
+    }
+
+    fn var_names(&self, prefix: &str) -> Vec<String> {
+        self.source.var_names(prefix)
+    }
+
+    fn contains(&self, name: &str) -> bool {
+        self.source.contains(name)
+    }
+
+    fn origin(&self, name: &str) -> Option<String> {
+        self.source.origin(name)
+    }
+
+    fn warn(&self, warning: EnvWarning) {
+        self.warnings.borrow_mut().push(warning.clone());
+        self.source.warn(warning);
+    }
+}
+
+/// Finds the name a field is read from: the first present one of its name and its aliases.
+///
+/// Reports a warning to the source if the field is deprecated and the found variable is set,
+/// or if the found variable is an alias of a field with deprecated aliases.
+///
+/// # Arguments
+///
+/// * `source` - The source to look the variables up in.
+/// * `name` - The name of the variable of the field.
+/// * `aliases` - The alternative names of the variable, tried in order.
+/// * `deprecated` - The message of `#[env(deprecated = "...")]`, deprecating the aliases if
+///   there are any, the variable itself otherwise.
+///
+/// # Returns
+///
+/// The found name, or `name` if no variable is set.
+pub fn resolve_env_name(
+    source: &dyn EnvSource,
+    name: String,
+    aliases: &[String],
+    deprecated: Option<&str>,
+) -> String {
+    if source.contains(&name) {
+        if let (Some(message), true) = (deprecated, aliases.is_empty()) {
+            source.warn(EnvWarning::DeprecatedVar {
+                var_name: name.clone(),
+                replacement: None,
+                message: message.to_string(),
+            });
+        }
+        return name;
+    }
+
+    let Some(alias) = aliases.iter().find(|alias| source.contains(alias)) else {
+        return name;
+    };
+    if let Some(message) = deprecated {
+        source.warn(EnvWarning::DeprecatedVar {
+            var_name: alias.clone(),
+            replacement: Some(name),
+            message: message.to_string(),
+        });
+    }
+    alias.clone()
+}
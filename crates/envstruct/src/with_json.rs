@@ -37,30 +37,61 @@ impl<T: for<'a> serde::de::Deserialize<'a>> WithJson<T> {
         var_name: impl AsRef<str>,
         default: Option<&str>,
     ) -> Result<T, EnvStructError>
+    where
+        Self: Sized,
+    {
+        Self::parse_from_source(&ProcessEnv, var_name, default)
+    }
+
+    /// Parses a JSON string from a variable of the given source into an instance of `T`.
+    ///
+    /// # Arguments
+    ///
+    /// * `source` - The source to look the variable up in.
+    /// * `var_name` - The name of the variable.
+    /// * `default` - An optional default value to use if the variable is not set.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(T)` if parsing is successful.
+    /// * `Err(EnvStructError)` if parsing fails or the variable is not set.
+    pub fn parse_from_source(
+        source: &dyn EnvSource,
+        var_name: impl AsRef<str>,
+        default: Option<&str>,
+    ) -> Result<T, EnvStructError>
     where
         Self: Sized,
     {
         let var_name = var_name.as_ref().to_string();
-        match std::env::var(&var_name) {
-            Ok(ref value) => Self::parse(value).map_err(|e| EnvStructError::ParseEnvError {
+        let field = || {
+            Box::new(FieldInfo::new(
+                std::any::type_name::<T>(),
+                Some("JSON".into()),
+            ))
+        };
+        match source.var(&var_name)? {
+            Some(ref value) => Self::parse(value).map_err(|e| EnvStructError::ParseEnvError {
+                origin: source.origin(&var_name),
                 var_name,
                 var_value: value.to_owned(),
+                field: field(),
                 source: e,
             }),
-            Err(e) => match default {
+            None => match default {
                 Some(default) => {
                     Self::parse(default).map_err(|e| EnvStructError::ParseDefaultError {
                         var_name,
                         var_value: default.to_owned(),
+                        field: field(),
                         source: e,
                     })
                 }
-                None => match e {
-                    std::env::VarError::NotPresent => Err(EnvStructError::MissingEnvVar(var_name)),
-                    std::env::VarError::NotUnicode(_) => {
-                        Err(EnvStructError::InvalidVarFormat(var_name))
-                    }
-                },
+                None => Err(EnvStructError::MissingEnvVar {
+                    var_name,
+                    suggestion: None,
+                    field: field(),
+                }),
             },
         }
     }
@@ -80,10 +111,10 @@ impl<T: for<'a> serde::de::Deserialize<'a>> WithJson<T> {
         prefix: impl AsRef<str>,
         default: Option<&str>,
     ) -> Result<Vec<EnvEntry>, EnvStructError> {
-        Ok(vec![EnvEntry {
-            name: prefix.as_ref().to_string(),
-            typ: std::any::type_name::<Self>().to_string(),
-            default: default.map(|v| v.to_string()),
-        }])
+        Ok(vec![EnvEntry::new(
+            prefix.as_ref(),
+            std::any::type_name::<Self>(),
+            default,
+        )])
     }
 }
@@ -0,0 +1,11 @@
+use envstruct::prelude::*;
+
+#[derive(EnvStruct)]
+pub struct Config {
+    #[env(flatten)]
+    port: u16,
+    #[env(flatten)]
+    name: Option<String>,
+}
+
+fn main() {}
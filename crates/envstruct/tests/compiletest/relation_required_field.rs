@@ -0,0 +1,11 @@
+use envstruct::prelude::*;
+
+#[derive(EnvStruct)]
+pub struct Config {
+    #[env(group = "auth", exactly_one)]
+    pub token: String,
+    #[env(exactly_one)]
+    pub password: Option<String>,
+}
+
+fn main() {}
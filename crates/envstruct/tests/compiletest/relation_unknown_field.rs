@@ -0,0 +1,12 @@
+use envstruct::prelude::*;
+
+#[derive(EnvStruct)]
+pub struct Config {
+    pub mode: String,
+    #[env(required_if(field = "kind", value = "remote"))]
+    pub endpoint: Option<String>,
+    #[env(conflicts_with = "mode")]
+    pub tls_cert: Option<String>,
+}
+
+fn main() {}
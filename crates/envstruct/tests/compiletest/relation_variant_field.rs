@@ -0,0 +1,16 @@
+use envstruct::prelude::*;
+
+#[derive(EnvStruct)]
+pub enum Storage {
+    Local {
+        path: String,
+    },
+    S3 {
+        #[env(conflicts_with = "nope")]
+        bucket: Option<String>,
+        #[env(group = "auth")]
+        key: Option<String>,
+    },
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use envstruct::prelude::*;
+
+#[derive(EnvStruct)]
+pub struct Config {
+    #[env(skip, default = 8080)]
+    port: u16,
+}
+
+fn main() {}
@@ -0,0 +1,35 @@
+use envstruct::prelude::*;
+
+#[derive(EnvStruct)]
+#[env(prefix = "APP", rename_all = "kebab-case", separator = "__", file_fallback, tag = "type")]
+pub enum Mode {
+    Local,
+    Remote,
+}
+
+#[derive(EnvStruct)]
+#[env(validate_with = Self::check)]
+pub enum Level {
+    Debug,
+    Info,
+}
+
+impl Level {
+    fn check(&self) -> Result<(), String> {
+        Ok(())
+    }
+}
+
+#[derive(EnvStruct)]
+#[env(case_sensitive, from_str)]
+pub struct Config {
+    pub mode: String,
+}
+
+#[derive(EnvStruct)]
+#[env(from_str)]
+pub enum Storage {
+    Local { path: String },
+}
+
+fn main() {}
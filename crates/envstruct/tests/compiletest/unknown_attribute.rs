@@ -0,0 +1,9 @@
+use envstruct::prelude::*;
+
+#[derive(EnvStruct)]
+pub struct Config {
+    #[env(defualt = 8080)]
+    port: u16,
+}
+
+fn main() {}
@@ -0,0 +1,129 @@
+#![allow(dead_code)]
+use envstruct::prelude::*;
+
+#[derive(EnvStruct, Debug)]
+pub struct Config {
+    pub db: DB,
+}
+
+#[derive(EnvStruct, Debug)]
+pub struct DB {
+    /// URL of the database.
+    #[env(alias = "DSN", alias = "DATABASE_URL", deprecated = "use DB_URL")]
+    pub url: String,
+
+    #[env(alias = "MAX_CONN")]
+    pub pool_size: Option<u32>,
+
+    #[env(deprecated = "the timeout is ignored")]
+    pub timeout: Option<u32>,
+}
+
+#[test]
+fn test_alias() {
+    let source = MapSource::new([
+        ("APP_DB_URL", "postgres://new"),
+        ("APP_DB_DSN", "postgres://old"),
+    ]);
+    let (config, warnings) = Config::from_source_with_warnings(&source, "APP").unwrap();
+    assert_eq!(config.db.url, "postgres://new");
+    assert!(warnings.is_empty());
+
+    let source = MapSource::new([
+        ("APP_DB_DATABASE_URL", "postgres://other"),
+        ("APP_DB_MAX_CONN", "10"),
+    ]);
+    let (config, warnings) = Config::from_source_with_warnings(&source, "APP").unwrap();
+    assert_eq!(config.db.url, "postgres://other");
+    assert_eq!(config.db.pool_size, Some(10));
+    assert_eq!(
+        warnings,
+        vec![EnvWarning::DeprecatedVar {
+            var_name: "APP_DB_DATABASE_URL".to_string(),
+            replacement: Some("APP_DB_URL".to_string()),
+            message: "use DB_URL".to_string(),
+        }]
+    );
+    assert_eq!(
+        warnings[0].to_string(),
+        "Environment variable `APP_DB_DATABASE_URL` is deprecated in favor of `APP_DB_URL`, use DB_URL"
+    );
+}
+
+#[test]
+fn test_alias_order() {
+    let source = MapSource::new([
+        ("APP_DB_DSN", "postgres://dsn"),
+        ("APP_DB_DATABASE_URL", "postgres://other"),
+        ("APP_DB_TIMEOUT", "5"),
+    ]);
+    let (config, warnings) = Config::from_source_with_warnings(&source, "APP").unwrap();
+    assert_eq!(config.db.url, "postgres://dsn");
+    assert_eq!(config.db.timeout, Some(5));
+
+    let deprecated: Vec<_> = warnings
+        .iter()
+        .map(|EnvWarning::DeprecatedVar { var_name, .. }| var_name.as_str())
+        .collect();
+    assert_eq!(deprecated, vec!["APP_DB_DSN", "APP_DB_TIMEOUT"]);
+}
+
+#[test]
+fn test_alias_errors() {
+    let source = MapSource::new([
+        ("APP_DB_DSN", "postgres://dsn"),
+        ("APP_DB_MAX_CONN", "many"),
+    ]);
+    let err = Config::from_source(&source, "APP").unwrap_err();
+    assert!(
+        matches!(err, EnvStructError::ParseEnvError { ref var_name, .. } if var_name == "APP_DB_MAX_CONN")
+    );
+
+    let err = Config::from_source(&MapSource::new([("APP_DB_MAX_CONN", "1")]), "APP").unwrap_err();
+    assert!(
+        matches!(err, EnvStructError::MissingEnvVar { ref var_name, .. } if var_name == "APP_DB_URL")
+    );
+}
+
+#[test]
+fn test_alias_strict() {
+    let source = MapSource::new([("APP_DB_DSN", "postgres://dsn"), ("APP_DB_MAX_CONN", "1")]);
+    assert!(Config::from_source_strict(&source, "APP").is_ok());
+}
+
+#[test]
+fn test_alias_usage() {
+    let entries = Config::get_env_entries("APP", None).unwrap();
+    assert_eq!(
+        entries[0].aliases,
+        vec!["APP_DB_DSN", "APP_DB_DATABASE_URL"]
+    );
+    assert_eq!(entries[0].deprecated.as_deref(), Some("use DB_URL"));
+
+    let usage = Config::usage_with_prefix("APP").unwrap();
+    println!("{usage}");
+    assert!(usage.contains("APP_DB_DSN (deprecated alias)"));
+    assert!(usage.contains("APP_DB_MAX_CONN (alias)"));
+    assert!(usage.contains("APP_DB_TIMEOUT (deprecated)"));
+    assert!(usage.contains("Deprecated: use DB_URL"));
+}
+
+#[derive(EnvStruct, Debug)]
+pub struct OptionalConfig {
+    pub db: Option<DB>,
+}
+
+#[test]
+fn test_alias_optional_nested() {
+    let source = MapSource::new([("APP_DB_DSN", "postgres://dsn")]);
+    let (config, warnings) = OptionalConfig::from_source_with_warnings(&source, "APP").unwrap();
+    assert_eq!(config.db.unwrap().url, "postgres://dsn");
+    let deprecated: Vec<_> = warnings
+        .iter()
+        .map(|EnvWarning::DeprecatedVar { var_name, .. }| var_name.as_str())
+        .collect();
+    assert_eq!(deprecated, vec!["APP_DB_DSN"]);
+
+    let config = OptionalConfig::from_source(&MapSource::new([("APP_OTHER", "1")]), "APP").unwrap();
+    assert!(config.db.is_none());
+}
@@ -0,0 +1,72 @@
+#![allow(dead_code)]
+use envstruct::prelude::*;
+
+#[derive(EnvStruct, Debug)]
+pub struct Config {
+    pub db: DB,
+    pub port: u16,
+    pub workers: u8,
+    pub limits: EnvMap<String, u32>,
+}
+
+#[derive(EnvStruct, Debug)]
+pub struct DB {
+    pub host: String,
+    pub port: u16,
+}
+
+#[test]
+fn test_all_errors() {
+    let source = MapSource::new([
+        ("APP_DB_PORT", "http"),
+        ("APP_WORKERS", "300"),
+        ("APP_LIMITS_CPU", "2"),
+        ("APP_LIMITS_MEMORY", "lots"),
+    ]);
+
+    let errors = Config::from_source_all_errors(&source, "APP").unwrap_err();
+    println!("{errors}");
+    assert_eq!(errors.len(), 5);
+
+    let errors: Vec<_> = errors.into_iter().collect();
+    assert!(
+        matches!(&errors[0], EnvStructError::MissingEnvVar { var_name, .. } if var_name == "APP_DB_HOST")
+    );
+    assert!(
+        matches!(&errors[1], EnvStructError::ParseEnvError { var_name, .. } if var_name == "APP_DB_PORT")
+    );
+    assert!(
+        matches!(&errors[2], EnvStructError::MissingEnvVar { var_name, .. } if var_name == "APP_PORT")
+    );
+    assert!(
+        matches!(&errors[3], EnvStructError::ParseEnvError { var_name, .. } if var_name == "APP_WORKERS")
+    );
+    assert!(
+        matches!(&errors[4], EnvStructError::ParseEnvError { var_name, .. } if var_name == "APP_LIMITS_MEMORY")
+    );
+}
+
+#[test]
+fn test_first_error() {
+    let source = MapSource::new([("APP_DB_PORT", "http")]);
+
+    let err = Config::from_source(&source, "APP").unwrap_err();
+    assert!(
+        matches!(err, EnvStructError::MissingEnvVar { var_name, .. } if var_name == "APP_DB_HOST")
+    );
+}
+
+#[test]
+fn test_no_errors() {
+    let source = MapSource::new([
+        ("APP_DB_HOST", "localhost"),
+        ("APP_DB_PORT", "5432"),
+        ("APP_PORT", "80"),
+        ("APP_WORKERS", "4"),
+    ]);
+
+    let config = Config::from_source_all_errors(&source, "APP").unwrap();
+    assert_eq!(config.db.port, 5432);
+    assert_eq!(config.workers, 4);
+    assert!(config.limits.is_empty());
+}
@@ -0,0 +1,106 @@
+#![allow(dead_code)]
+use envstruct::prelude::*;
+
+#[derive(EnvStruct, Debug)]
+pub struct Config {
+    pub host: String,
+    pub port: u16,
+    pub greeting: String,
+    pub literal: String,
+    pub certificate: String,
+    #[env(default = "info")]
+    pub log_level: String,
+}
+
+const DOTENV: &str = r#"
+# database settings
+export APP_HOST=localhost   # trailing comment
+APP_PORT = 5432
+APP_GREETING="hello\tworld\n\"quoted\" \$HOME"
+APP_LITERAL='no \n escapes # here'
+APP_CERTIFICATE="-----BEGIN-----
+line
+-----END-----"
+APP_URL=http://example.com/#anchor
+"#;
+
+#[test]
+fn test_dotenv_parsing() {
+    let source = DotEnv::parse(".env", DOTENV).unwrap();
+
+    let config = Config::from_source(&source, "APP").unwrap();
+    assert_eq!(config.host, "localhost");
+    assert_eq!(config.port, 5432);
+    assert_eq!(config.greeting, "hello\tworld\n\"quoted\" $HOME");
+    assert_eq!(config.literal, r"no \n escapes # here");
+    assert_eq!(config.certificate, "-----BEGIN-----\nline\n-----END-----");
+    assert_eq!(config.log_level, "info");
+    assert_eq!(
+        source.var("APP_URL").unwrap().as_deref(),
+        Some("http://example.com/#anchor")
+    );
+}
+
+#[test]
+fn test_dotenv_layered_under_source() {
+    let dotenv = DotEnv::parse(".env", DOTENV).unwrap();
+    let source =
+        MapSource::new([("APP_PORT", "8080"), ("APP_LOG_LEVEL", "debug")]).fallback(dotenv);
+
+    let config = Config::from_source(&source, "APP").unwrap();
+    assert_eq!(config.host, "localhost");
+    assert_eq!(config.port, 8080);
+    assert_eq!(config.log_level, "debug");
+}
+
+#[test]
+fn test_dotenv_error_origin() {
+    let source = DotEnv::parse(".env", "APP_HOST=localhost\n\nAPP_PORT=http\n").unwrap();
+
+    let err = u16::parse_from_source(&source, "APP_PORT", None).unwrap_err();
+    assert!(matches!(
+        &err,
+        EnvStructError::ParseEnvError { origin: Some(origin), .. } if origin == ".env:3"
+    ));
+    assert!(err.to_string().contains("from .env:3"));
+}
+
+#[test]
+fn test_dotenv_syntax_errors() {
+    let err = DotEnv::parse(".env", "APP_HOST=localhost\nAPP_PORT\n").unwrap_err();
+    assert!(matches!(
+        err,
+        EnvStructError::DotEnvSyntaxError { line: 2, .. }
+    ));
+
+    let err = DotEnv::parse(".env", "APP_HOST=\"localhost\n").unwrap_err();
+    assert!(matches!(
+        err,
+        EnvStructError::DotEnvSyntaxError { line: 1, .. }
+    ));
+
+    let err = DotEnv::parse(".env", "\nAPP HOST=localhost\n").unwrap_err();
+    assert!(matches!(
+        err,
+        EnvStructError::DotEnvSyntaxError { line: 2, .. }
+    ));
+}
+
+#[test]
+fn test_dotenv_load() {
+    let path = std::env::temp_dir().join(format!("envstruct-{}.env", std::process::id()));
+    std::fs::write(&path, "APP_HOST=example.com\n").unwrap();
+    let source = DotEnv::load(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(
+        source.var("APP_HOST").unwrap().as_deref(),
+        Some("example.com")
+    );
+    assert_eq!(
+        source.origin("APP_HOST"),
+        Some(format!("{}:1", path.display()))
+    );
+
+    let err = DotEnv::load(&path).unwrap_err();
+    assert!(matches!(err, EnvStructError::ReadFileError { .. }));
+}
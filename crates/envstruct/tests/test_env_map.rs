@@ -0,0 +1,78 @@
+#![allow(dead_code)]
+use envstruct::prelude::*;
+use std::collections::HashMap;
+
+#[derive(EnvStruct, Debug)]
+pub struct Config {
+    pub labels: EnvMap<String, String>,
+    pub limits: EnvBTreeMap<LowercaseKey, u32>,
+}
+
+#[test]
+fn test_env_map_prefix_boundary() {
+    let source = MapSource::new([
+        ("APP_LABELS_TEAM", "core"),
+        ("APP_LABELSX_TEAM", "other"),
+        ("APP_LABELS", "none"),
+        ("APP_LABELS_", "empty"),
+    ]);
+    let config = Config::from_source(&source, "APP").unwrap();
+    assert_eq!(config.labels.len(), 1);
+    assert_eq!(config.labels.get("TEAM"), Some(&"core".to_string()));
+}
+
+#[test]
+fn test_env_map_key_case() {
+    let source = MapSource::new([
+        ("APP_LABELS_Team", "core"),
+        ("APP_LIMITS_CPU", "2"),
+        ("APP_LIMITS_Memory", "512"),
+    ]);
+    let config = Config::from_source(&source, "APP").unwrap();
+    assert_eq!(config.labels.get("Team"), Some(&"core".to_string()));
+    assert_eq!(config.limits.get("cpu"), Some(&2));
+    let limits: Vec<_> = config
+        .limits
+        .iter()
+        .map(|(key, value)| (key.to_string(), *value))
+        .collect();
+    assert_eq!(
+        limits,
+        vec![("cpu".to_string(), 2), ("memory".to_string(), 512)]
+    );
+}
+
+#[test]
+fn test_env_map_duplicate_key() {
+    let source = MapSource::new([
+        ("APP_LIMITS_CPU", "2"),
+        ("APP_LIMITS_cpu", "4"),
+        ("APP_LIMITS_MEMORY", "512"),
+    ]);
+    let err = Config::from_source(&source, "APP").unwrap_err();
+    assert_eq!(err.kind(), "duplicate_key");
+    assert_eq!(
+        err.to_string(),
+        "Configuration from environment variables failed. Variables `APP_LIMITS_CPU`, \
+         `APP_LIMITS_cpu` have the same key"
+    );
+
+    let source = MapSource::new([("APP_1", "a"), ("APP_01", "b")]);
+    let err = EnvMap::<u8, String>::parse_from_source(&source, "APP", None).unwrap_err();
+    assert!(matches!(
+        err,
+        EnvStructError::DuplicateKey { ref var_names }
+            if var_names == &vec!["APP_01".to_string(), "APP_1".to_string()]
+    ));
+}
+
+#[test]
+fn test_env_map_shape() {
+    let EnvMap(map) = EnvMap(HashMap::from([("a".to_string(), 1)]));
+    assert_eq!(map.get("a"), Some(&1));
+    let debug = format!(
+        "{:?}",
+        EnvBTreeMap(std::collections::BTreeMap::from([(1, 2)]))
+    );
+    assert_eq!(debug, "EnvBTreeMap({1: 2})");
+}
@@ -0,0 +1,87 @@
+#![allow(dead_code)]
+use envstruct::prelude::*;
+
+#[derive(EnvStruct, Debug)]
+pub struct Config {
+    pub db: DB,
+    #[env(default = "ten")]
+    pub workers: u8,
+    pub tags: Vec<String>,
+}
+
+#[derive(EnvStruct, Debug)]
+pub struct DB {
+    pub host: String,
+    pub port: u16,
+    #[env(secret)]
+    pub password: Secret<String>,
+}
+
+#[test]
+fn test_error_field_path() {
+    let source = MapSource::new([("APP_DB_PORT", "http"), ("APP_DB_PASSWORD", "s3cr3t")]);
+
+    let errors: Vec<_> = Config::from_source_all_errors(&source, "APP")
+        .unwrap_err()
+        .into_iter()
+        .collect();
+
+    let fields: Vec<_> = errors.iter().map(|err| err.field().unwrap()).collect();
+    assert_eq!(
+        fields
+            .iter()
+            .map(|field| (
+                field.path.as_str(),
+                field.typ.as_str(),
+                field.hint.as_deref()
+            ))
+            .collect::<Vec<_>>(),
+        vec![
+            ("Config.db.host", "String", None),
+            ("Config.db.port", "u16", Some("0-65535")),
+            ("Config.workers", "u8", Some("0-255")),
+            ("Config.tags", "Vec<String>", Some("comma-separated values")),
+        ]
+    );
+
+    assert_eq!(
+        errors[1].to_string(),
+        "Configuration from environment variables failed. `APP_DB_PORT` of `Config.db.port` \
+         unable to parse value `http`, expected u16 (0-65535), invalid digit found in string"
+    );
+    assert_eq!(
+        errors[2].to_string(),
+        "Configuration from environment variables failed. `APP_WORKERS` of `Config.workers` \
+         unable to parse default value `ten`, expected u8 (0-255), invalid digit found in string"
+    );
+}
+
+#[test]
+fn test_error_field_path_of_first_error() {
+    let source = MapSource::new([
+        ("APP_DB_HOST", "localhost"),
+        ("APP_DB_PORT", "5432"),
+        ("APP_WORKERS", "4"),
+        ("APP_TAGS", "a,b"),
+    ]);
+
+    let err = Config::from_source(&source, "APP").unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Configuration from environment variables failed. Environment variable \
+         `APP_DB_PASSWORD` of `Config.db.password` is not present, expected String"
+    );
+}
+
+#[test]
+fn test_error_without_field() {
+    let source = MapSource::new([("PORT", "70000")]);
+
+    let err = u16::parse_from_source(&source, "PORT", None).unwrap_err();
+    assert_eq!(err.field().unwrap().path, "");
+    assert_eq!(
+        err.to_string(),
+        "Configuration from environment variables failed. `PORT` unable to parse value `70000`, \
+         expected u16 (0-65535), number too large to fit in target type"
+    );
+}
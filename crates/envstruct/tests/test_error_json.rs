@@ -0,0 +1,113 @@
+#![allow(dead_code)]
+use envstruct::prelude::*;
+use serde_json::json;
+
+#[derive(EnvStruct, Debug)]
+pub struct Config {
+    pub db: DB,
+    pub port: u16,
+}
+
+#[derive(EnvStruct, Debug)]
+pub struct DB {
+    pub host: String,
+    #[env(secret)]
+    pub password: String,
+}
+
+#[test]
+fn test_error_json() {
+    let source = MapSource::new([
+        ("APP_DB_HSOT", "localhost"),
+        ("APP_DB_PASSWORD", "s3cr3t"),
+        ("APP_PORT", "http"),
+    ]);
+
+    let errors = Config::from_source_all_errors(&source, "APP").unwrap_err();
+    let json = serde_json::to_value(&errors).unwrap();
+    let messages: Vec<String> = errors.iter().map(ToString::to_string).collect();
+
+    assert_eq!(
+        json,
+        json!([
+            {
+                "kind": "missing_var",
+                "message": messages[0],
+                "var_name": "APP_DB_HOST",
+                "suggestion": "APP_DB_HSOT",
+                "field_path": "Config.db.host",
+                "type": "String",
+                "hint": null,
+            },
+            {
+                "kind": "parse_error",
+                "message": messages[1],
+                "var_name": "APP_PORT",
+                "value": "http",
+                "origin": null,
+                "field_path": "Config.port",
+                "type": "u16",
+                "hint": "0-65535",
+            },
+        ])
+    );
+}
+
+#[test]
+fn test_error_json_redacted() {
+    #[derive(EnvStruct, Debug)]
+    pub struct Auth {
+        #[env(secret)]
+        pub pin: u32,
+    }
+
+    let source = MapSource::new([("APP_PIN", "s3cr3t")]);
+    let err = Auth::from_source(&source, "APP").unwrap_err();
+
+    let json = serde_json::to_value(&err).unwrap();
+    assert_eq!(json["kind"], "parse_error");
+    assert_eq!(json["value"], REDACTED);
+    assert!(!json.to_string().contains("s3cr3t"));
+}
+
+#[test]
+fn test_error_json_multiple() {
+    let mut errors = EnvStructErrors::new();
+    errors.push(EnvStructError::UnknownEnvVar(vec!["APP_PROT".to_string()]));
+    errors.push(EnvStructError::InvalidKeyFormat("=".to_string()));
+    let err = EnvStructError::from(errors);
+
+    let json = serde_json::to_value(&err).unwrap();
+    assert_eq!(json["kind"], "multiple");
+    assert_eq!(json["errors"][0]["kind"], "unknown_vars");
+    assert_eq!(json["errors"][0]["var_names"], json!(["APP_PROT"]));
+    assert_eq!(json["errors"][1]["kind"], "invalid_key_format");
+    assert_eq!(json["errors"][1]["value"], "=");
+}
+
+#[test]
+fn test_error_json_validation() {
+    #[derive(EnvStruct, Debug)]
+    pub struct Pool {
+        #[env(min = 10)]
+        pub size: u32,
+    }
+
+    let source = MapSource::new([("APP_SIZE", "5")]);
+    let err = Pool::from_source(&source, "APP").unwrap_err();
+
+    let json = serde_json::to_value(&err).unwrap();
+    assert_eq!(
+        json,
+        json!({
+            "kind": "validation_error",
+            "message": err.to_string(),
+            "var_name": "APP_SIZE",
+            "rule": "min = 10",
+            "description": "must be at least 10",
+            "field_path": "Pool.size",
+            "type": "u32",
+            "hint": null,
+        })
+    );
+}
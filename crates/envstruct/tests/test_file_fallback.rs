@@ -0,0 +1,138 @@
+#![allow(dead_code)]
+use envstruct::prelude::*;
+use std::path::PathBuf;
+
+fn secret_file(name: &str, content: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("envstruct-{}-{name}", std::process::id()));
+    std::fs::write(&path, content).unwrap();
+    path
+}
+
+#[test]
+fn test_file_fallback_field() {
+    #[derive(EnvStruct, Debug)]
+    pub struct Config {
+        pub user: String,
+        #[env(file_fallback)]
+        pub password: String,
+        #[env(file_fallback)]
+        pub token: String,
+    }
+
+    let path = secret_file("password", "s3cr3t\n");
+    let source = MapSource::new([
+        ("APP_USER", "admin"),
+        ("APP_PASSWORD_FILE", path.to_str().unwrap()),
+        ("APP_TOKEN", "plain"),
+        ("APP_TOKEN_FILE", "/nonexistent"),
+    ]);
+
+    let config = Config::from_source(&source, "APP").unwrap();
+    assert_eq!(config.user, "admin");
+    assert_eq!(config.password, "s3cr3t");
+    assert_eq!(config.token, "plain");
+    std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn test_file_fallback_struct() {
+    #[derive(EnvStruct, Debug)]
+    #[env(file_fallback)]
+    pub struct Config {
+        pub password: String,
+        pub port: u16,
+    }
+
+    let password = secret_file("struct-password", "multi\nline\r\n");
+    let port = secret_file("struct-port", "http\n");
+    let source = MapSource::new([
+        ("APP_PASSWORD_FILE", password.to_str().unwrap()),
+        ("APP_PORT_FILE", port.to_str().unwrap()),
+    ]);
+
+    let err = Config::from_source(&source, "APP").unwrap_err();
+    assert!(matches!(
+        &err,
+        EnvStructError::ParseEnvError { var_name, origin: Some(origin), .. }
+            if var_name == "APP_PORT" && origin.contains("APP_PORT_FILE")
+    ));
+
+    std::fs::write(&port, "8080\n").unwrap();
+    let config = Config::from_source(&source, "APP").unwrap();
+    assert_eq!(config.password, "multi\nline");
+    assert_eq!(config.port, 8080);
+
+    std::fs::remove_file(password).unwrap();
+    std::fs::remove_file(port).unwrap();
+}
+
+#[test]
+fn test_file_fallback_errors() {
+    #[derive(EnvStruct, Debug)]
+    pub struct Config {
+        pub password: String,
+    }
+
+    let source = FileFallback(MapSource::new([(
+        "APP_PASSWORD_FILE",
+        "/nonexistent/password",
+    )]));
+
+    let err = Config::from_source(&source, "APP").unwrap_err();
+    println!("{err}");
+    assert!(matches!(
+        &err,
+        EnvStructError::ReadVarFileError { var_name, file_var_name, path, .. }
+            if var_name == "APP_PASSWORD"
+                && file_var_name == "APP_PASSWORD_FILE"
+                && path == "/nonexistent/password"
+    ));
+    assert_eq!(err.field().unwrap().path, "Config.password");
+    assert_eq!(err.field().unwrap().typ, "String");
+
+    let source = FileFallback(MapSource::default());
+    let err = Config::from_source(&source, "APP").unwrap_err();
+    assert!(matches!(err, EnvStructError::MissingEnvVar { .. }));
+}
+
+#[test]
+fn test_file_fallback_provenance() {
+    #[derive(EnvStruct, Debug)]
+    pub struct Config {
+        #[env(file_fallback)]
+        pub pw: String,
+        pub db: Option<Db>,
+    }
+
+    #[derive(EnvStruct, Debug)]
+    #[env(file_fallback)]
+    pub struct Db {
+        pub password: String,
+    }
+
+    let pw = secret_file("provenance-pw", "s3cr3t\n");
+    let db_password = secret_file("provenance-db", "hunter2\n");
+    let source = MapSource::new([
+        ("APP_PW_FILE", pw.to_str().unwrap()),
+        ("APP_DB_PASSWORD_FILE", db_password.to_str().unwrap()),
+    ]);
+
+    let config = Config::from_source(&source, "APP").unwrap();
+    assert_eq!(config.db.unwrap().password, "hunter2");
+
+    let provenance = Config::provenance(&source, "APP").unwrap();
+    assert_eq!(provenance[0].0.name, "APP_PW");
+    assert!(matches!(
+        &provenance[0].1,
+        Provenance::Source(Some(origin)) if origin.contains("APP_PW_FILE")
+    ));
+    assert!(matches!(&provenance[1].1, Provenance::Source(Some(_))));
+
+    let dump = Config::dump(&source, "APP").unwrap();
+    println!("{dump}");
+    assert!(dump.contains(r#""s3cr3t""#));
+    assert!(!dump.contains("missing"));
+
+    std::fs::remove_file(pw).unwrap();
+    std::fs::remove_file(db_password).unwrap();
+}
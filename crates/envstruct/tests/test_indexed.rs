@@ -0,0 +1,159 @@
+#![allow(dead_code)]
+use envstruct::prelude::*;
+use std::collections::BTreeMap;
+
+#[derive(EnvStruct, Debug)]
+pub struct Config {
+    pub servers: Vec<Server>,
+    pub replicas: BTreeMap<usize, Server>,
+    pub backup: Option<Vec<Server>>,
+}
+
+#[derive(EnvStruct, Debug, PartialEq)]
+pub struct Server {
+    pub host: String,
+    #[env(default = 80)]
+    pub port: u16,
+}
+
+fn server(host: &str, port: u16) -> Server {
+    Server {
+        host: host.to_string(),
+        port,
+    }
+}
+
+#[test]
+fn test_indexed() {
+    let source = MapSource::new([
+        ("APP_SERVERS_0_HOST", "a"),
+        ("APP_SERVERS_1_HOST", "b"),
+        ("APP_SERVERS_1_PORT", "8080"),
+        ("APP_SERVERS_10_HOST", "k"),
+        ("APP_SERVERS_2_HOST", "c"),
+        ("APP_SERVERS_3_HOST", "d"),
+        ("APP_SERVERS_4_HOST", "e"),
+        ("APP_SERVERS_5_HOST", "f"),
+        ("APP_SERVERS_6_HOST", "g"),
+        ("APP_SERVERS_7_HOST", "h"),
+        ("APP_SERVERS_8_HOST", "i"),
+        ("APP_SERVERS_9_HOST", "j"),
+        ("APP_REPLICAS_3_HOST", "x"),
+        ("APP_REPLICAS_7_HOST", "y"),
+    ]);
+    let config = Config::from_source(&source, "APP").unwrap();
+    assert_eq!(config.servers.len(), 11);
+    assert_eq!(config.servers[0], server("a", 80));
+    assert_eq!(config.servers[1], server("b", 8080));
+    assert_eq!(config.servers[10], server("k", 80));
+    assert_eq!(
+        config.replicas,
+        BTreeMap::from([(3, server("x", 80)), (7, server("y", 80))])
+    );
+    assert_eq!(config.backup, None);
+
+    let source = MapSource::new([("APP_BACKUP_0_HOST", "z")]);
+    let config = Config::from_source(&source, "APP").unwrap();
+    assert!(config.servers.is_empty());
+    assert_eq!(config.backup, Some(vec![server("z", 80)]));
+}
+
+#[test]
+fn test_indexed_gap() {
+    let source = MapSource::new([("APP_SERVERS_0_HOST", "a"), ("APP_SERVERS_2_HOST", "c")]);
+    let err = Config::from_source(&source, "APP").unwrap_err();
+    assert_eq!(err.kind(), "index_gap");
+    assert_eq!(
+        err.to_string(),
+        "Configuration from environment variables failed. No variables under `APP_SERVERS_1` of \
+         `Config.servers` but under `APP_SERVERS_2`, the elements of a list are numbered from 0 \
+         without gaps"
+    );
+}
+
+#[test]
+fn test_indexed_errors() {
+    let source = MapSource::new([
+        ("APP_SERVERS_0_PORT", "80"),
+        ("APP_SERVERS_1_HOST", "b"),
+        ("APP_SERVERS_1_PORT", "http"),
+    ]);
+    let errors = Config::from_source_all_errors(&source, "APP").unwrap_err();
+    let paths: Vec<_> = errors
+        .iter()
+        .map(|err| err.field().unwrap().path.as_str())
+        .collect();
+    assert_eq!(
+        paths,
+        vec!["Config.servers.0.host", "Config.servers.1.port"]
+    );
+}
+
+#[test]
+fn test_indexed_no_suggestion_from_other_elements() {
+    let source = MapSource::new([("APP_SERVERS_0_HOST", "a"), ("APP_SERVERS_1_PORT", "81")]);
+    let err = Config::from_source(&source, "APP").unwrap_err();
+    assert!(matches!(
+        err,
+        EnvStructError::MissingEnvVar {
+            ref var_name,
+            suggestion: None,
+            ..
+        } if var_name == "APP_SERVERS_1_HOST"
+    ));
+}
+
+#[test]
+fn test_indexed_strict() {
+    let source = MapSource::new([
+        ("APP_SERVERS_0_HOST", "a"),
+        ("APP_SERVERS_0_NAME", "a"),
+        ("APP_SERVERS_X_HOST", "a"),
+    ]);
+    let err = Config::from_source_strict(&source, "APP").unwrap_err();
+    assert!(matches!(
+        err,
+        EnvStructError::UnknownEnvVar(ref names)
+            if names == &vec!["APP_SERVERS_0_NAME".to_string(), "APP_SERVERS_X_HOST".to_string()]
+    ));
+}
+
+#[test]
+fn test_indexed_usage() {
+    let entries = Config::get_env_entries("APP", None).unwrap();
+    let names: Vec<_> = entries.iter().map(|entry| entry.name.as_str()).collect();
+    assert_eq!(
+        names,
+        vec![
+            "APP_SERVERS_<N>_HOST",
+            "APP_SERVERS_<N>_PORT",
+            "APP_REPLICAS_<N>_HOST",
+            "APP_REPLICAS_<N>_PORT",
+            "APP_BACKUP_<N>_HOST",
+            "APP_BACKUP_<N>_PORT",
+        ]
+    );
+    assert!(entries[0].matches("APP_SERVERS_12_HOST"));
+    assert!(!entries[0].matches("APP_SERVERS__HOST"));
+}
+
+#[test]
+fn test_indexed_provenance() {
+    let source = MapSource::new([("APP_SERVERS_0_HOST", "a"), ("APP_SERVERS_1_HOST", "b")]);
+    let provenance: Vec<_> = Config::provenance(&source, "APP")
+        .unwrap()
+        .into_iter()
+        .map(|(entry, provenance)| (entry.name, provenance))
+        .collect();
+    assert_eq!(
+        provenance,
+        vec![
+            ("APP_SERVERS_0_HOST".to_string(), Provenance::Source(None)),
+            ("APP_SERVERS_1_HOST".to_string(), Provenance::Source(None)),
+        ]
+    );
+
+    let dump = Config::dump(&source, "APP").unwrap();
+    assert!(dump.contains(r#"APP_SERVERS_1_HOST | "b""#), "{dump}");
+    assert!(!dump.contains("missing"), "{dump}");
+}
@@ -0,0 +1,156 @@
+#![allow(dead_code)]
+use envstruct::prelude::*;
+use std::collections::HashMap;
+
+#[derive(EnvStruct, Debug)]
+pub struct Config {
+    pub db: HashMap<String, Db>,
+}
+
+#[derive(EnvStruct, Debug, PartialEq)]
+pub struct Db {
+    pub host: String,
+    #[env(default = 5432)]
+    pub port: u16,
+    pub tls_cert: Option<String>,
+}
+
+fn db(host: &str, port: u16) -> Db {
+    Db {
+        host: host.to_string(),
+        port,
+        tls_cert: None,
+    }
+}
+
+#[test]
+fn test_keyed() {
+    let source = MapSource::new([
+        ("APP_DB_TENANT1_HOST", "a"),
+        ("APP_DB_TENANT1_PORT", "6543"),
+        ("APP_DB_TENANT2_HOST", "b"),
+        ("APP_DB_EU_WEST_HOST", "c"),
+        ("APP_DB_EU_WEST_TLS_CERT", "cert"),
+    ]);
+    let config = Config::from_source(&source, "APP").unwrap();
+    assert_eq!(
+        config.db,
+        HashMap::from([
+            ("TENANT1".to_string(), db("a", 6543)),
+            ("TENANT2".to_string(), db("b", 5432)),
+            (
+                "EU_WEST".to_string(),
+                Db {
+                    tls_cert: Some("cert".to_string()),
+                    ..db("c", 5432)
+                }
+            ),
+        ])
+    );
+
+    let config = Config::from_source(&MapSource::new([("OTHER", "x")]), "APP").unwrap();
+    assert!(config.db.is_empty());
+}
+
+#[test]
+fn test_keyed_errors() {
+    let source = MapSource::new([
+        ("APP_DB_A_PORT", "1"),
+        ("APP_DB_B_HOST", "b"),
+        ("APP_DB_B_PORT", "http"),
+    ]);
+    let errors = Config::from_source_all_errors(&source, "APP").unwrap_err();
+    let paths: Vec<_> = errors
+        .iter()
+        .map(|err| err.field().unwrap().path.as_str())
+        .collect();
+    assert_eq!(paths, vec!["Config.db.A.host", "Config.db.B.port"]);
+}
+
+#[test]
+fn test_keyed_key_format() {
+    let source = MapSource::new([("APP_1_HOST", "a"), ("APP_X_HOST", "b")]);
+    let err = HashMap::<u8, Db>::parse_from_source(&source, "APP", None).unwrap_err();
+    assert!(matches!(err, EnvStructError::InvalidKeyFormat(ref key) if key == "APP_X"));
+}
+
+#[test]
+fn test_keyed_no_suggestion_from_other_fields() {
+    let source = MapSource::new([("APP_DB_US_PORT", "6543")]);
+    let err = Config::from_source(&source, "APP").unwrap_err();
+    assert!(matches!(
+        err,
+        EnvStructError::MissingEnvVar {
+            ref var_name,
+            suggestion: None,
+            ..
+        } if var_name == "APP_DB_US_HOST"
+    ));
+}
+
+#[test]
+fn test_keyed_strict() {
+    let source = MapSource::new([
+        ("APP_DB_A_HOST", "a"),
+        ("APP_DB_A_NAME", "a"),
+        ("APP_DBX_HOST", "a"),
+    ]);
+    let err = Config::from_source_strict(&source, "APP").unwrap_err();
+    assert!(matches!(
+        err,
+        EnvStructError::UnknownEnvVar(ref names)
+            if names == &vec!["APP_DBX_HOST".to_string(), "APP_DB_A_NAME".to_string()]
+    ));
+}
+
+#[test]
+fn test_keyed_usage() {
+    let entries = Config::get_env_entries("APP", None).unwrap();
+    let names: Vec<_> = entries.iter().map(|entry| entry.name.as_str()).collect();
+    assert_eq!(
+        names,
+        vec![
+            "APP_DB_<NAME>_HOST",
+            "APP_DB_<NAME>_PORT",
+            "APP_DB_<NAME>_TLS_CERT"
+        ]
+    );
+    assert!(entries[0].matches("APP_DB_EU_WEST_HOST"));
+    assert!(!entries[0].matches("APP_DB__HOST"));
+    assert!(!entries[0].matches("APP_DB_HOST"));
+}
+
+#[test]
+fn test_keyed_lowercase_key() {
+    let source = MapSource::new([("APP_DB_tenant1_HOST", "a"), ("APP_DB_eu_west_PORT", "1")]);
+    let errors = Config::from_source_all_errors(&source, "APP").unwrap_err();
+    let names: Vec<_> = errors
+        .iter()
+        .map(|err| match err {
+            EnvStructError::MissingEnvVar { var_name, .. } => var_name.as_str(),
+            err => panic!("{err}"),
+        })
+        .collect();
+    assert_eq!(names, vec!["APP_DB_eu_west_HOST"]);
+
+    let source = MapSource::new([("APP_DB_tenant1_HOST", "a")]);
+    let config = Config::from_source(&source, "APP").unwrap();
+    assert_eq!(config.db.get("tenant1"), Some(&db("a", 5432)));
+}
+
+#[test]
+fn test_keyed_provenance() {
+    let source = MapSource::new([("APP_DB_eu_west_HOST", "a"), ("APP_DB_EU_PORT", "1")]);
+    let provenance: Vec<_> = Config::provenance(&source, "APP")
+        .unwrap()
+        .into_iter()
+        .map(|(entry, provenance)| (entry.name, provenance))
+        .collect();
+    assert_eq!(
+        provenance,
+        vec![
+            ("APP_DB_eu_west_HOST".to_string(), Provenance::Source(None)),
+            ("APP_DB_EU_PORT".to_string(), Provenance::Source(None)),
+        ]
+    );
+}
@@ -0,0 +1,76 @@
+#![allow(dead_code)]
+use envstruct::prelude::*;
+
+#[derive(EnvStruct, Debug)]
+pub struct Config {
+    pub host: String,
+    pub port: u16,
+    #[env(default = "info")]
+    pub log_level: String,
+    pub user: Option<String>,
+    pub labels: EnvMap<String, String>,
+}
+
+#[test]
+fn test_layers_precedence() {
+    let layers = Layers::new()
+        .layer(
+            "defaults",
+            MapSource::new([("APP_HOST", "localhost"), ("APP_PORT", "80")]),
+        )
+        .layer(
+            ".env",
+            DotEnv::parse(".env", "APP_PORT=8080\nAPP_LABELS_TEAM=core\n").unwrap(),
+        )
+        .layer("overrides", MapSource::new([("APP_PORT", "9090")]));
+
+    let config = Config::from_source(&layers, "APP").unwrap();
+    assert_eq!(config.host, "localhost");
+    assert_eq!(config.port, 9090);
+    assert_eq!(config.log_level, "info");
+    assert_eq!(config.labels.get("TEAM"), Some(&"core".to_string()));
+
+    assert_eq!(layers.layer_of("APP_HOST"), Some("defaults"));
+    assert_eq!(layers.layer_of("APP_PORT"), Some("overrides"));
+    assert_eq!(layers.layer_of("APP_USER"), None);
+    assert_eq!(
+        layers.origin("APP_LABELS_TEAM").as_deref(),
+        Some(".env (.env:2)")
+    );
+}
+
+#[test]
+fn test_provenance() {
+    let layers = Layers::new()
+        .layer("defaults", MapSource::new([("APP_HOST", "localhost")]))
+        .layer(
+            ".env",
+            DotEnv::parse(".env", "APP_PORT=8080\nAPP_LABELS_TEAM=core\n").unwrap(),
+        );
+
+    let provenance: Vec<_> = Config::provenance(&layers, "APP")
+        .unwrap()
+        .into_iter()
+        .map(|(entry, provenance)| (entry.name, provenance))
+        .collect();
+
+    assert_eq!(
+        provenance,
+        vec![
+            (
+                "APP_HOST".to_string(),
+                Provenance::Source(Some("defaults".to_string()))
+            ),
+            (
+                "APP_PORT".to_string(),
+                Provenance::Source(Some(".env (.env:1)".to_string()))
+            ),
+            ("APP_LOG_LEVEL".to_string(), Provenance::Default),
+            ("APP_USER".to_string(), Provenance::Missing),
+            (
+                "APP_LABELS_TEAM".to_string(),
+                Provenance::Source(Some(".env (.env:2)".to_string()))
+            ),
+        ]
+    );
+}
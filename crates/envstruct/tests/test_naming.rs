@@ -0,0 +1,113 @@
+#![allow(dead_code)]
+use envstruct::prelude::*;
+
+#[derive(EnvStruct, Debug)]
+#[env(separator = "__")]
+pub struct Config {
+    pub db: DB,
+    pub cache: Cache,
+    pub log_level: String,
+    pub labels: EnvMap<String, String>,
+}
+
+#[derive(EnvStruct, Debug)]
+pub struct DB {
+    pub host: String,
+    pub max_connections: u32,
+}
+
+#[derive(EnvStruct, Debug)]
+#[env(rename_all = "kebab-case")]
+pub struct Cache {
+    pub ttl_secs: u64,
+}
+
+#[test]
+fn test_separator() {
+    let source = MapSource::new([
+        ("APP__DB__HOST", "localhost"),
+        ("APP__DB__MAX_CONNECTIONS", "10"),
+        ("app__cache__ttl-secs", "60"),
+        ("APP__LOG_LEVEL", "debug"),
+        ("APP__LABELS__TEAM", "core"),
+    ]);
+
+    let config = Config::from_source(&source, "APP").unwrap();
+    assert_eq!(config.db.host, "localhost");
+    assert_eq!(config.db.max_connections, 10);
+    assert_eq!(config.cache.ttl_secs, 60);
+    assert_eq!(config.log_level, "debug");
+    assert_eq!(config.labels.get("TEAM"), Some(&"core".to_string()));
+}
+
+#[test]
+fn test_naming_usage() {
+    let names: Vec<_> = Config::get_env_entries("APP", None)
+        .unwrap()
+        .into_iter()
+        .map(|entry| entry.name)
+        .collect();
+    assert_eq!(
+        names,
+        vec![
+            "APP__DB__HOST",
+            "APP__DB__MAX_CONNECTIONS",
+            "app__cache__ttl-secs",
+            "APP__LOG_LEVEL",
+            "APP__LABELS__*",
+        ]
+    );
+
+    let usage = Config::usage_with_prefix("APP").unwrap();
+    assert!(usage.contains("APP__DB__MAX_CONNECTIONS"));
+    assert!(usage.contains("app__cache__ttl-secs"));
+}
+
+#[test]
+fn test_rename_all() {
+    #[derive(EnvStruct, Debug)]
+    #[env(rename_all = "preserve")]
+    pub struct Preserved {
+        pub db: DB,
+        #[env(name = "Port")]
+        pub port: u16,
+    }
+
+    #[derive(EnvStruct, Debug)]
+    #[env(rename_all = "lowercase")]
+    pub struct Lower {
+        pub port: u16,
+        pub db: DB,
+    }
+
+    let source = MapSource::new([
+        ("app_db_host", "localhost"),
+        ("app_db_max_connections", "10"),
+        ("app_Port", "80"),
+        ("app_port", "81"),
+    ]);
+
+    let config = Preserved::from_source(&source, "app").unwrap();
+    assert_eq!(config.db.host, "localhost");
+    assert_eq!(config.port, 80);
+
+    let config = Lower::from_source(&source, "APP").unwrap();
+    assert_eq!(config.db.max_connections, 10);
+    assert_eq!(config.port, 81);
+}
+
+#[test]
+fn test_naming_policy() {
+    let naming = EnvNaming::default();
+    assert_eq!(naming.join("app", "db_host"), "APP_DB_HOST");
+    assert_eq!(concat_env_name("app", "db_host"), "APP_DB_HOST");
+    assert_eq!(naming.join("", "db_host"), "DB_HOST");
+    assert_eq!(naming.join("app", ""), "APP");
+
+    let naming = naming.with_case(NameCase::Kebab);
+    assert_eq!(naming.join("my-app", "db_host"), "my-app-db-host");
+    assert_eq!(naming.join("MY_APP", "db_host"), "my_app-db-host");
+
+    let naming = naming.with_case(NameCase::Preserve).with_separator("__");
+    assert_eq!(naming.join("App", "db_host"), "App__db_host");
+}
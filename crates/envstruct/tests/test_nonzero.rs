@@ -45,3 +45,13 @@ fn test_non_zero() {
         ));
     }
 }
+
+#[test]
+fn test_integer_hints() {
+    use std::num::{NonZeroI8, NonZeroU32};
+
+    assert_eq!(u8::hint().as_deref(), Some("0-255"));
+    assert_eq!(i8::hint().as_deref(), Some("-128 to 127"));
+    assert_eq!(NonZeroU32::hint().as_deref(), Some("1-4294967295"));
+    assert_eq!(NonZeroI8::hint().as_deref(), Some("-128 to 127 except 0"));
+}
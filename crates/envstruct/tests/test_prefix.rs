@@ -0,0 +1,54 @@
+#![allow(dead_code)]
+use envstruct::prelude::*;
+use std::env;
+
+#[derive(EnvStruct, Debug)]
+#[env(prefix = "PREFIX_TEST_APP")]
+pub struct Config {
+    pub db: DB,
+    #[env(name = "CACHE")]
+    pub cache_db: DB,
+    #[env(default = 8080)]
+    pub port: u16,
+}
+
+#[derive(EnvStruct, Debug)]
+#[env(prefix = "DATABASE")]
+pub struct DB {
+    pub host: String,
+}
+
+#[test]
+fn test_default_prefix() {
+    env::set_var("PREFIX_TEST_APP_DATABASE_HOST", "localhost");
+    env::set_var("PREFIX_TEST_APP_CACHE_HOST", "cache");
+
+    let config = Config::new().unwrap();
+    assert_eq!(config.db.host, "localhost");
+    assert_eq!(config.cache_db.host, "cache");
+    assert_eq!(config.port, 8080);
+}
+
+#[test]
+fn test_prefix_override() {
+    let source = MapSource::new([
+        ("OTHER_DATABASE_HOST", "localhost"),
+        ("OTHER_CACHE_HOST", "cache"),
+    ]);
+
+    let config = Config::from_source(&source, "OTHER").unwrap();
+    assert_eq!(config.db.host, "localhost");
+    assert_eq!(config.cache_db.host, "cache");
+}
+
+#[test]
+fn test_prefix_usage() {
+    let usage = Config::usage().unwrap();
+    assert!(usage.contains("PREFIX_TEST_APP_DATABASE_HOST"));
+    assert!(usage.contains("PREFIX_TEST_APP_CACHE_HOST"));
+    assert!(usage.contains("PREFIX_TEST_APP_PORT"));
+
+    let usage = Config::usage_with_prefix("OTHER").unwrap();
+    assert!(usage.contains("OTHER_DATABASE_HOST"));
+    assert!(!usage.contains("PREFIX_TEST_APP"));
+}
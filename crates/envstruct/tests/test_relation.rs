@@ -0,0 +1,142 @@
+#![allow(dead_code)]
+use envstruct::prelude::*;
+
+#[derive(EnvStruct, Debug)]
+pub struct Config {
+    #[env(default = "local")]
+    pub mode: String,
+
+    #[env(required_if(field = "mode", value = "remote"))]
+    pub endpoint: Option<String>,
+
+    #[env(conflicts_with = "tls_insecure")]
+    pub tls_cert: Option<String>,
+    pub tls_insecure: Option<bool>,
+
+    #[env(group = "auth", exactly_one)]
+    pub password: Option<String>,
+    #[env(group = "auth")]
+    pub token: Option<String>,
+}
+
+fn relation_error(source: &MapSource) -> (String, Vec<String>, String) {
+    match Config::from_source(source, "APP").unwrap_err() {
+        EnvStructError::RelationError {
+            rule,
+            var_names,
+            message,
+        } => (rule, var_names, message),
+        err => panic!("unexpected error: {err}"),
+    }
+}
+
+#[test]
+fn test_relation_ok() {
+    let source = MapSource::new([
+        ("APP_MODE", "remote"),
+        ("APP_ENDPOINT", "https://example.com"),
+        ("APP_TLS_CERT", "cert.pem"),
+        ("APP_TOKEN", "secret"),
+    ]);
+    let config = Config::from_source(&source, "APP").unwrap();
+    assert_eq!(config.endpoint.as_deref(), Some("https://example.com"));
+
+    let config = Config::from_source(&MapSource::new([("APP_PASSWORD", "secret")]), "APP").unwrap();
+    assert_eq!(config.mode, "local");
+}
+
+#[test]
+fn test_required_if() {
+    let source = MapSource::new([("APP_MODE", "remote"), ("APP_TOKEN", "secret")]);
+    let (rule, var_names, message) = relation_error(&source);
+    assert_eq!(rule, "required_if");
+    assert_eq!(var_names, vec!["APP_ENDPOINT", "APP_MODE"]);
+    assert_eq!(
+        message,
+        "`APP_ENDPOINT` is required when `APP_MODE` is `remote`"
+    );
+}
+
+#[test]
+fn test_conflicts_with() {
+    let source = MapSource::new([
+        ("APP_TLS_CERT", "cert.pem"),
+        ("APP_TLS_INSECURE", "true"),
+        ("APP_TOKEN", "secret"),
+    ]);
+    let (rule, var_names, message) = relation_error(&source);
+    assert_eq!(rule, "conflicts_with");
+    assert_eq!(var_names, vec!["APP_TLS_CERT", "APP_TLS_INSECURE"]);
+    assert_eq!(
+        message,
+        "`APP_TLS_CERT` conflicts with `APP_TLS_INSECURE`, set only one of them"
+    );
+}
+
+#[test]
+fn test_group() {
+    let (rule, var_names, message) = relation_error(&MapSource::new([("APP_MODE", "local")]));
+    assert_eq!(rule, "group = auth");
+    assert_eq!(var_names, vec!["APP_PASSWORD", "APP_TOKEN"]);
+    assert_eq!(
+        message,
+        "Exactly one of `APP_PASSWORD`, `APP_TOKEN` of group `auth` must be set, none is set"
+    );
+
+    let source = MapSource::new([("APP_PASSWORD", "secret"), ("APP_TOKEN", "secret")]);
+    let (_, var_names, message) = relation_error(&source);
+    assert_eq!(var_names, vec!["APP_PASSWORD", "APP_TOKEN"]);
+    assert_eq!(
+        message,
+        "Exactly one of `APP_PASSWORD`, `APP_TOKEN` of group `auth` may be set, `APP_PASSWORD`, `APP_TOKEN` are set"
+    );
+}
+
+#[test]
+fn test_relation_all_errors() {
+    let source = MapSource::new([
+        ("APP_MODE", "remote"),
+        ("APP_TLS_CERT", "cert.pem"),
+        ("APP_TLS_INSECURE", "1"),
+    ]);
+    let errors = Config::from_source_all_errors(&source, "APP").unwrap_err();
+    let kinds: Vec<_> = errors.iter().map(EnvStructError::kind).collect();
+    assert_eq!(kinds, vec!["parse_error"]);
+
+    let source = MapSource::new([
+        ("APP_MODE", "remote"),
+        ("APP_TLS_CERT", "cert.pem"),
+        ("APP_TLS_INSECURE", "true"),
+    ]);
+    let errors = Config::from_source_all_errors(&source, "APP").unwrap_err();
+    let rules: Vec<_> = errors
+        .iter()
+        .map(|err| match err {
+            EnvStructError::RelationError { rule, .. } => rule.as_str(),
+            err => panic!("unexpected error: {err}"),
+        })
+        .collect();
+    assert_eq!(rules, vec!["required_if", "conflicts_with", "group = auth"]);
+}
+
+#[test]
+fn test_relation_usage() {
+    let entries = Config::get_env_entries("APP", None).unwrap();
+    assert_eq!(
+        entries[1].constraints,
+        vec!["required if `APP_MODE` is `remote`"]
+    );
+    assert_eq!(
+        entries[2].constraints,
+        vec!["conflicts with `APP_TLS_INSECURE`"]
+    );
+    assert_eq!(
+        entries[4].constraints,
+        vec!["group `auth`: exactly one of `APP_PASSWORD`, `APP_TOKEN`"]
+    );
+    assert_eq!(entries[5].constraints, entries[4].constraints);
+
+    let usage = Config::usage_with_prefix("APP").unwrap();
+    println!("{usage}");
+    assert!(usage.contains("Constraints: required if `APP_MODE` is `remote`"));
+}
@@ -0,0 +1,86 @@
+#![allow(dead_code)]
+use envstruct::prelude::*;
+
+#[derive(EnvStruct, Debug)]
+pub struct Config {
+    pub user: String,
+    pub password: Secret<String>,
+    #[env(default = "1234")]
+    pub pin: Secret<u16>,
+    pub token: Option<Secret<String>>,
+}
+
+#[test]
+fn test_secret_parsing() {
+    let source = MapSource::new([("APP_USER", "admin"), ("APP_PASSWORD", "s3cr3t")]);
+
+    let config = Config::from_source(&source, "APP").unwrap();
+    assert_eq!(config.password.expose(), "s3cr3t");
+    assert_eq!(*config.pin.expose(), 1234);
+    assert!(config.token.is_none());
+
+    let debug = format!("{config:?}");
+    assert!(!debug.contains("s3cr3t"));
+    assert!(!debug.contains("1234"));
+    assert_eq!(config.password.to_string(), REDACTED);
+}
+
+#[test]
+fn test_secret_errors() {
+    let source = MapSource::new([
+        ("APP_USER", "admin"),
+        ("APP_PASSWORD", "s3cr3t"),
+        ("APP_PIN", "not-a-pin"),
+    ]);
+
+    let err = Config::from_source(&source, "APP").unwrap_err();
+    assert!(!err.to_string().contains("not-a-pin"));
+    assert!(matches!(
+        err,
+        EnvStructError::ParseEnvError { var_value, .. } if var_value == REDACTED
+    ));
+
+    #[derive(EnvStruct, Debug)]
+    pub struct BadDefault {
+        #[env(default = "not-a-pin")]
+        pub pin: Secret<u16>,
+    }
+
+    let err = BadDefault::from_source(&MapSource::default(), "APP").unwrap_err();
+    assert!(!err.to_string().contains("not-a-pin"));
+}
+
+#[test]
+fn test_secret_usage() {
+    let usage = Config::usage_with_prefix("APP").unwrap();
+    println!("{usage}");
+    assert!(usage.contains("Secret<u16>"));
+    assert!(!usage.contains("1234"));
+    assert!(usage.contains(REDACTED));
+}
+
+#[test]
+fn test_secret_errors_quoting_value() {
+    #[derive(EnvStruct, Debug)]
+    pub struct Tokens {
+        pub id: Secret<std::num::NonZeroU32>,
+        pub ids: Secret<Vec<std::num::NonZeroU32>>,
+    }
+
+    let source = MapSource::new([("APP_ID", "tok3n"), ("APP_IDS", "1,s3cr3t")]);
+    let errors = Tokens::from_source_all_errors(&source, "APP").unwrap_err();
+    assert_eq!(errors.iter().count(), 2);
+    for err in &errors {
+        let message = err.to_string();
+        assert!(!message.contains("tok3n"), "{message}");
+        assert!(!message.contains("s3cr3t"), "{message}");
+        assert!(message.contains("expected"), "{message}");
+    }
+
+    #[cfg(feature = "serde_json")]
+    {
+        let json = serde_json::to_string(&errors).unwrap();
+        assert!(!json.contains("tok3n"));
+        assert!(!json.contains("s3cr3t"));
+    }
+}
@@ -0,0 +1,66 @@
+#![allow(dead_code)]
+use envstruct::prelude::*;
+use std::path::PathBuf;
+
+#[derive(EnvStruct, Debug)]
+pub struct Config {
+    pub db_host: String,
+    pub db_password: String,
+    pub api_token: Option<String>,
+}
+
+fn secrets_dir(name: &str, files: &[(&str, &str)]) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("envstruct-{}-{name}", std::process::id()));
+    std::fs::create_dir_all(dir.join("nested")).unwrap();
+    for (file_name, content) in files {
+        std::fs::write(dir.join(file_name), content).unwrap();
+    }
+    dir
+}
+
+#[test]
+fn test_secrets_dir() {
+    let dir = secrets_dir(
+        "secrets",
+        &[
+            ("db_password", "s3cr3t\n"),
+            ("API_TOKEN", "token"),
+            (".hidden", "ignored"),
+        ],
+    );
+
+    let secrets = SecretsDir::load(&dir).unwrap().with_prefix("APP");
+    let source = MapSource::new([("APP_DB_HOST", "localhost")]).fallback(&secrets);
+
+    let config = Config::from_source(&source, "APP").unwrap();
+    assert_eq!(config.db_host, "localhost");
+    assert_eq!(config.db_password, "s3cr3t");
+    assert_eq!(config.api_token.as_deref(), Some("token"));
+
+    assert_eq!(
+        secrets.var_names("APP"),
+        vec!["APP_API_TOKEN".to_string(), "APP_DB_PASSWORD".to_string()]
+    );
+    assert_eq!(
+        secrets.origin("APP_DB_PASSWORD"),
+        Some(dir.join("db_password").display().to_string())
+    );
+
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn test_secrets_dir_errors() {
+    let err = SecretsDir::load("/nonexistent/secrets").unwrap_err();
+    assert!(matches!(err, EnvStructError::ReadFileError { .. }));
+
+    let dir = secrets_dir("missing", &[("db_host", "localhost")]);
+    let source = SecretsDir::load(&dir).unwrap().with_prefix("APP");
+
+    let err = Config::from_source(&source, "APP").unwrap_err();
+    assert!(
+        matches!(err, EnvStructError::MissingEnvVar { var_name, .. } if var_name == "APP_DB_PASSWORD")
+    );
+
+    std::fs::remove_dir_all(dir).unwrap();
+}
@@ -0,0 +1,83 @@
+#![allow(dead_code)]
+use envstruct::prelude::*;
+use std::collections::HashMap;
+
+#[derive(EnvStruct, Debug)]
+pub struct Config {
+    pub db: DB,
+
+    #[env(default = 8080)]
+    pub port: u16,
+
+    pub tags: EnvMap<String, String>,
+}
+
+#[derive(EnvStruct, Debug)]
+pub struct DB {
+    pub host: String,
+    pub user: Option<String>,
+}
+
+#[test]
+fn test_map_source() {
+    let source = MapSource::new([
+        ("APP_DB_HOST", "localhost"),
+        ("APP_TAGS_ENV", "prod"),
+        ("APP_TAGS_REGION", "eu"),
+    ]);
+
+    let config = Config::from_source(&source, "APP").unwrap();
+    assert_eq!(config.db.host, "localhost");
+    assert_eq!(config.db.user, None);
+    assert_eq!(config.port, 8080);
+    assert_eq!(config.tags.len(), 2);
+    assert_eq!(config.tags.get("ENV"), Some(&"prod".to_string()));
+    assert_eq!(config.tags.get("REGION"), Some(&"eu".to_string()));
+}
+
+#[test]
+fn test_hash_map_source() {
+    let source = HashMap::from([
+        ("APP_DB_HOST".to_string(), "localhost".to_string()),
+        ("APP_DB_USER".to_string(), "admin".to_string()),
+        ("APP_PORT".to_string(), "5432".to_string()),
+    ]);
+
+    let config = Config::from_source(&source, "APP").unwrap();
+    assert_eq!(config.db.host, "localhost");
+    assert_eq!(config.db.user.as_deref(), Some("admin"));
+    assert_eq!(config.port, 5432);
+    assert!(config.tags.is_empty());
+}
+
+#[test]
+fn test_source_errors() {
+    let source: MapSource = [("APP_PORT", "http")].into_iter().collect();
+
+    let res = Config::from_source(&source, "APP");
+    assert!(matches!(
+        res.err().unwrap(),
+        EnvStructError::MissingEnvVar { .. }
+    ));
+
+    let source = MapSource::new([("APP_DB_HOST", "localhost"), ("APP_PORT", "http")]);
+    let res = Config::from_source(&source, "APP");
+    assert!(matches!(
+        res.err().unwrap(),
+        EnvStructError::ParseEnvError { .. }
+    ));
+}
+
+#[test]
+fn test_primitive_from_source() {
+    let source = MapSource::new([("PORT", "80")]);
+    assert_eq!(u16::parse_from_source(&source, "PORT", None).unwrap(), 80);
+    assert_eq!(
+        u16::parse_from_source(&source, "OTHER", Some("81")).unwrap(),
+        81
+    );
+    assert_eq!(
+        Option::<u16>::parse_from_source(&source, "OTHER", None).unwrap(),
+        None
+    );
+}
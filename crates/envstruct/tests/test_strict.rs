@@ -0,0 +1,66 @@
+#![allow(dead_code)]
+use envstruct::prelude::*;
+
+#[derive(EnvStruct, Debug)]
+pub struct Config {
+    pub db: DB,
+    pub log_file: Option<String>,
+    pub labels: EnvMap<String, String>,
+}
+
+#[derive(EnvStruct, Debug)]
+pub struct DB {
+    #[env(default = "localhost")]
+    pub host: String,
+    pub password: Option<String>,
+}
+
+#[test]
+fn test_strict_unknown_vars() {
+    let source = MapSource::new([
+        ("APP_DB_HSOT", "db.example.com"),
+        ("APP_DB_PASSWORD_FILE", "/run/secrets/db_password"),
+        ("APP_LOG_FILE", "/var/log/app.log"),
+        ("APP_LABELS_TEAM", "core"),
+        ("APP_TIMEOUT", "10"),
+        ("APPLICATION", "other"),
+        ("OTHER_DB_HOST", "other"),
+    ]);
+
+    // the typo is silently ignored without strict mode
+    let config = Config::from_source(&source, "APP").unwrap();
+    assert_eq!(config.db.host, "localhost");
+
+    let err = Config::from_source_strict(&source, "APP").unwrap_err();
+    println!("{err}");
+    assert!(matches!(
+        &err,
+        EnvStructError::UnknownEnvVar(names) if names == &["APP_DB_HSOT", "APP_TIMEOUT"]
+    ));
+    assert!(err.to_string().contains("`APP_DB_HSOT`, `APP_TIMEOUT`"));
+}
+
+#[test]
+fn test_strict_ok() {
+    let source = MapSource::new([
+        ("APP_DB_HOST", "db.example.com"),
+        ("APP_LABELS_TEAM", "core"),
+        ("OTHER", "value"),
+    ]);
+
+    let config = Config::from_source_strict(&source, "APP").unwrap();
+    assert_eq!(config.db.host, "db.example.com");
+    assert!(Config::unknown_vars(&source, "APP").unwrap().is_empty());
+}
+
+#[test]
+fn test_strict_parse_error_first() {
+    #[derive(EnvStruct, Debug)]
+    pub struct Port {
+        pub port: u16,
+    }
+
+    let source = MapSource::new([("APP_PROT", "80")]);
+    let err = Port::from_source_strict(&source, "APP").unwrap_err();
+    assert!(matches!(err, EnvStructError::MissingEnvVar { .. }));
+}
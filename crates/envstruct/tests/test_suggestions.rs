@@ -0,0 +1,65 @@
+#![allow(dead_code)]
+use envstruct::prelude::*;
+
+#[derive(EnvStruct, Debug)]
+pub struct Config {
+    pub db: DB,
+}
+
+#[derive(EnvStruct, Debug)]
+pub struct DB {
+    pub host: String,
+    pub port: u16,
+    pub user: String,
+}
+
+#[test]
+fn test_did_you_mean() {
+    let source = MapSource::new([
+        ("MY_APP_DB_HSOT", "localhost"),
+        ("MY_APP_DB_PORT", "5432"),
+        ("MYAPP_DB_USER", "admin"),
+    ]);
+
+    let errors = Config::from_source_all_errors(&source, "MY_APP").unwrap_err();
+    let suggestions: Vec<_> = errors
+        .iter()
+        .map(|err| match err {
+            EnvStructError::MissingEnvVar {
+                var_name,
+                suggestion,
+                ..
+            } => (var_name.as_str(), suggestion.as_deref()),
+            err => panic!("unexpected error {err}"),
+        })
+        .collect();
+    assert_eq!(
+        suggestions,
+        vec![
+            ("MY_APP_DB_HOST", Some("MY_APP_DB_HSOT")),
+            ("MY_APP_DB_USER", Some("MYAPP_DB_USER")),
+        ]
+    );
+
+    let err = Config::from_source(&source, "MY_APP").unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Configuration from environment variables failed. Environment variable \
+         `MY_APP_DB_HOST` of `Config.db.host` is not present, expected String, did you mean \
+         `MY_APP_DB_HSOT`?"
+    );
+}
+
+#[test]
+fn test_no_suggestion_from_other_fields() {
+    let source = MapSource::new([("MY_APP_DB_PORT", "5432"), ("MY_APP_DB_USER", "admin")]);
+
+    let err = Config::from_source(&source, "MY_APP").unwrap_err();
+    assert!(matches!(
+        err,
+        EnvStructError::MissingEnvVar {
+            suggestion: None,
+            ..
+        }
+    ));
+}
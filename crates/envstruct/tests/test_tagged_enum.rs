@@ -0,0 +1,149 @@
+#![allow(dead_code)]
+use envstruct::prelude::*;
+
+#[derive(EnvStruct, Debug)]
+pub struct Config {
+    pub storage: Storage,
+    #[env(default = "memory")]
+    pub cache: Cache,
+}
+
+#[derive(EnvStruct, Debug, PartialEq)]
+pub enum Storage {
+    S3 {
+        bucket: String,
+        #[env(default = "us-east-1")]
+        region: String,
+    },
+    FileSystem(FsConfig),
+    #[env(rename = "none")]
+    Disabled,
+}
+
+#[derive(EnvStruct, Debug, PartialEq)]
+pub struct FsConfig {
+    pub root: String,
+}
+
+#[derive(EnvStruct, Debug, PartialEq)]
+#[env(tag = "TYPE")]
+pub enum Cache {
+    Memory,
+    Redis { url: String },
+}
+
+#[test]
+fn test_tagged_enum() {
+    let source = MapSource::new([
+        ("APP_STORAGE_KIND", "s3"),
+        ("APP_STORAGE_S3_BUCKET", "assets"),
+    ]);
+    let config = Config::from_source(&source, "APP").unwrap();
+    assert_eq!(
+        config.storage,
+        Storage::S3 {
+            bucket: "assets".to_string(),
+            region: "us-east-1".to_string()
+        }
+    );
+    assert_eq!(config.cache, Cache::Memory);
+
+    let source = MapSource::new([
+        ("APP_STORAGE_KIND", "File_System"),
+        ("APP_STORAGE_FILE_SYSTEM_ROOT", "/data"),
+        ("APP_CACHE_TYPE", "redis"),
+        ("APP_CACHE_REDIS_URL", "redis://localhost"),
+    ]);
+    let config = Config::from_source(&source, "APP").unwrap();
+    assert_eq!(
+        config.storage,
+        Storage::FileSystem(FsConfig {
+            root: "/data".to_string()
+        })
+    );
+    assert_eq!(
+        config.cache,
+        Cache::Redis {
+            url: "redis://localhost".to_string()
+        }
+    );
+
+    let source = MapSource::new([("APP_STORAGE_KIND", "none")]);
+    let config = Config::from_source(&source, "APP").unwrap();
+    assert_eq!(config.storage, Storage::Disabled);
+}
+
+#[test]
+fn test_tagged_enum_errors() {
+    let source = MapSource::new([("APP_STORAGE_KIND", "s3")]);
+    let err = Config::from_source(&source, "APP").unwrap_err();
+    assert!(matches!(
+        err,
+        EnvStructError::MissingEnvVar { ref var_name, ref field, .. }
+            if var_name == "APP_STORAGE_S3_BUCKET" && field.path == "Config.storage.s3.bucket"
+    ));
+
+    let source = MapSource::new([("APP_STORAGE_KIND", "file_system")]);
+    let err = Config::from_source(&source, "APP").unwrap_err();
+    assert!(matches!(
+        err,
+        EnvStructError::MissingEnvVar { ref var_name, ref field, .. }
+            if var_name == "APP_STORAGE_FILE_SYSTEM_ROOT" && field.path == "Config.storage.file_system.0.root"
+    ));
+
+    let source = MapSource::new([("APP_STORAGE_KIND", "gcs")]);
+    let err = Config::from_source(&source, "APP").unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Configuration from environment variables failed. `APP_STORAGE_KIND` of `Config.storage` \
+         unable to parse value `gcs`, expected Storage (one of: s3, file_system, none), \
+         unknown variant `gcs`"
+    );
+
+    let err =
+        Config::from_source(&MapSource::new([("APP_CACHE_TYPE", "memory")]), "APP").unwrap_err();
+    assert!(matches!(
+        err,
+        EnvStructError::MissingEnvVar { ref var_name, .. } if var_name == "APP_STORAGE_KIND"
+    ));
+}
+
+#[test]
+fn test_tagged_enum_strict() {
+    let source = MapSource::new([
+        ("APP_STORAGE_KIND", "s3"),
+        ("APP_STORAGE_S3_BUCKET", "assets"),
+        ("APP_STORAGE_FILE_SYSTEM_ROOT", "/data"),
+    ]);
+    assert!(Config::from_source_strict(&source, "APP").is_ok());
+}
+
+#[test]
+fn test_tagged_enum_usage() {
+    let entries = Config::get_env_entries("APP", None).unwrap();
+    let names: Vec<_> = entries.iter().map(|entry| entry.name.as_str()).collect();
+    assert_eq!(
+        names,
+        vec![
+            "APP_STORAGE_KIND",
+            "APP_STORAGE_S3_BUCKET",
+            "APP_STORAGE_S3_REGION",
+            "APP_STORAGE_FILE_SYSTEM_ROOT",
+            "APP_CACHE_TYPE",
+            "APP_CACHE_REDIS_URL",
+        ]
+    );
+    assert_eq!(
+        entries[0].constraints,
+        vec!["one of: s3, file_system, none"]
+    );
+    assert_eq!(entries[4].default.as_deref(), Some("memory"));
+    assert_eq!(
+        entries[1].constraints,
+        vec!["used if `APP_STORAGE_KIND` is `s3`"]
+    );
+
+    let usage = Config::usage_with_prefix("APP").unwrap();
+    println!("{usage}");
+    assert!(usage.contains("Constraints: used if `APP_STORAGE_KIND` is `file_system`"));
+}
@@ -0,0 +1,103 @@
+#![allow(dead_code)]
+use envstruct::prelude::*;
+
+#[derive(EnvStruct, Debug, PartialEq)]
+pub enum RunMode {
+    Local,
+    #[env(alias = "prod")]
+    Remote,
+    #[env(rename = "test", alias = "fake")]
+    Mock,
+}
+
+#[derive(EnvStruct, Debug, PartialEq)]
+#[env(case_sensitive)]
+pub enum Level {
+    #[env(rename = "DEBUG")]
+    Debug,
+    #[env(rename = "INFO")]
+    Info,
+}
+
+#[derive(EnvStruct, Debug, PartialEq, strum::EnumString)]
+#[env(from_str)]
+pub enum Color {
+    #[strum(serialize = "r")]
+    Red,
+    #[strum(serialize = "g")]
+    Green,
+}
+
+#[derive(EnvStruct, Debug)]
+pub struct Config {
+    #[env(default = "local")]
+    pub mode: RunMode,
+    pub level: Option<Level>,
+    pub color: Option<Color>,
+}
+
+#[test]
+fn test_unit_enum() {
+    assert_eq!(RunMode::parse("local").unwrap(), RunMode::Local);
+    assert_eq!(RunMode::parse("REMOTE").unwrap(), RunMode::Remote);
+    assert_eq!(RunMode::parse(" Prod ").unwrap(), RunMode::Remote);
+    assert_eq!(RunMode::parse("test").unwrap(), RunMode::Mock);
+    assert_eq!(RunMode::parse("fake").unwrap(), RunMode::Mock);
+    assert!(RunMode::parse("mock").is_err());
+
+    assert_eq!(Level::parse("INFO").unwrap(), Level::Info);
+    assert!(Level::parse("info").is_err());
+
+    assert_eq!(Color::parse("g").unwrap(), Color::Green);
+    assert!(Color::parse("green").is_err());
+
+    let source = MapSource::new([("APP_LEVEL", "DEBUG"), ("APP_COLOR", "r")]);
+    let config = Config::from_source(&source, "APP").unwrap();
+    assert_eq!(config.mode, RunMode::Local);
+    assert_eq!(config.level, Some(Level::Debug));
+    assert_eq!(config.color, Some(Color::Red));
+}
+
+#[test]
+fn test_unit_enum_errors() {
+    let source = MapSource::new([("APP_MODE", "cloud")]);
+    let err = Config::from_source(&source, "APP").unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Configuration from environment variables failed. `APP_MODE` of `Config.mode` unable to \
+         parse value `cloud`, expected RunMode (one of: local, remote, test), unknown variant `cloud`"
+    );
+    assert_eq!(
+        RunMode::hint().as_deref(),
+        Some("one of: local, remote, test")
+    );
+    assert_eq!(Color::hint(), None);
+}
+
+#[test]
+fn test_unit_enum_usage() {
+    let entries = Config::get_env_entries("APP", None).unwrap();
+    assert_eq!(entries[0].constraints, vec!["one of: local, remote, test"]);
+    assert_eq!(entries[1].typ, std::any::type_name::<Option<Level>>());
+    assert_eq!(entries[1].constraints, vec!["one of: DEBUG, INFO"]);
+    assert!(entries[2].constraints.is_empty());
+
+    let usage = Config::usage_with_prefix("APP").unwrap();
+    println!("{usage}");
+    assert!(usage.contains("Constraints: one of: local, remote, test"));
+}
+
+#[test]
+fn test_unit_enum_from_str_migration() {
+    #[derive(EnvStruct, Debug, PartialEq, strum::EnumString)]
+    #[strum(serialize_all = "kebab-case")]
+    #[env(from_str)]
+    pub enum Access {
+        ReadOnly,
+        ReadWrite,
+    }
+
+    // variant names in snake case would accept `read_only` instead
+    assert_eq!(Access::parse("read-only").unwrap(), Access::ReadOnly);
+    assert!(Access::parse("read_only").is_err());
+}
@@ -0,0 +1,90 @@
+#![allow(dead_code)]
+use envstruct::prelude::*;
+
+#[derive(EnvStruct, Debug)]
+pub struct Config {
+    /// Address the HTTP server binds to.
+    #[env(default = "0.0.0.0")]
+    pub host: String,
+
+    #[env(help = "Port of the HTTP server", default = 8080)]
+    pub port: u16,
+
+    /// Database connection settings.
+    pub db: DB,
+
+    pub debug: bool,
+}
+
+#[derive(EnvStruct, Debug)]
+pub struct DB {
+    /// Connection string of the primary database, including credentials. It is required in every
+    /// environment and must point to a writable instance.
+    ///
+    /// Read replicas are configured separately.
+    pub dsn: String,
+
+    #[env(default = 10)]
+    pub pool: u8,
+}
+
+#[test]
+fn test_descriptions() {
+    let descriptions: Vec<_> = Config::get_env_entries("APP", None)
+        .unwrap()
+        .into_iter()
+        .map(|entry| (entry.name, entry.description))
+        .collect();
+
+    assert_eq!(
+        descriptions,
+        vec![
+            (
+                "APP_HOST".to_string(),
+                Some("Address the HTTP server binds to.".to_string())
+            ),
+            (
+                "APP_PORT".to_string(),
+                Some("Port of the HTTP server".to_string())
+            ),
+            (
+                "APP_DB_DSN".to_string(),
+                Some(
+                    "Connection string of the primary database, including credentials. It is \
+                     required in every environment and must point to a writable instance.\n\
+                     Read replicas are configured separately."
+                        .to_string()
+                )
+            ),
+            (
+                "APP_DB_POOL".to_string(),
+                Some("Database connection settings.".to_string())
+            ),
+            ("APP_DEBUG".to_string(), None),
+        ]
+    );
+}
+
+#[test]
+fn test_usage_description_column() {
+    let usage = Config::usage_with_prefix("APP").unwrap();
+    println!("usage: \n{usage}");
+
+    assert!(usage.contains("DESCRIPTION"));
+    assert!(usage.contains("Port of the HTTP server"));
+    assert!(usage
+        .lines()
+        .all(|line| !line
+            .contains("Connection string of the primary database, including credentials.")));
+    assert!(usage.contains("Read replicas are configured separately."));
+}
+
+#[test]
+fn test_entry_new() {
+    let mut entry = EnvEntry::new("APP_PORT", std::any::type_name::<u16>(), Some("8080"));
+    entry.description = Some("Port of the HTTP server".to_string());
+    assert_eq!(entry.name, "APP_PORT");
+    assert_eq!(entry.typ, "u16");
+    assert_eq!(entry.default.as_deref(), Some("8080"));
+    assert!(!entry.secret && entry.aliases.is_empty() && entry.constraints.is_empty());
+}
@@ -0,0 +1,147 @@
+#![allow(dead_code)]
+use envstruct::prelude::*;
+
+#[derive(EnvStruct, Debug)]
+pub struct Config {
+    #[env(default = 8080, min = 1024, max = 49151)]
+    pub port: u16,
+
+    /// Name of the service.
+    #[env(len(min = 1, max = 8))]
+    pub name: String,
+
+    #[env(one_of = ["debug", "info", "warn"])]
+    pub log_level: Option<String>,
+
+    #[env(regex = "^[a-z]+://")]
+    pub url: Option<String>,
+
+    #[env(validate = check_even)]
+    pub workers: Option<u32>,
+
+    #[env(secret, min = 8)]
+    pub pin: Option<u32>,
+}
+
+fn check_even(value: &u32) -> Result<(), String> {
+    match value % 2 {
+        0 => Ok(()),
+        _ => Err(format!("{value} is not even")),
+    }
+}
+
+fn validation_error(source: &MapSource) -> (String, String, String, String) {
+    match Config::from_source(source, "APP").unwrap_err() {
+        EnvStructError::ValidationError {
+            var_name,
+            rule,
+            message,
+            field,
+        } => (var_name, rule, message, field.path),
+        err => panic!("unexpected error: {err}"),
+    }
+}
+
+#[test]
+fn test_validate_ok() {
+    let source = MapSource::new([
+        ("APP_NAME", "api"),
+        ("APP_LOG_LEVEL", "info"),
+        ("APP_URL", "https://example.com"),
+        ("APP_WORKERS", "4"),
+    ]);
+    let config = Config::from_source(&source, "APP").unwrap();
+    assert_eq!(config.port, 8080);
+    assert_eq!(config.name, "api");
+    assert_eq!(config.workers, Some(4));
+}
+
+#[test]
+fn test_validate_min_max() {
+    let source = MapSource::new([("APP_NAME", "api"), ("APP_PORT", "80")]);
+    assert_eq!(
+        validation_error(&source),
+        (
+            "APP_PORT".to_string(),
+            "min = 1024".to_string(),
+            "must be at least 1024".to_string(),
+            "Config.port".to_string()
+        )
+    );
+
+    let source = MapSource::new([("APP_NAME", "api"), ("APP_PORT", "50000")]);
+    let (_, rule, _, _) = validation_error(&source);
+    assert_eq!(rule, "max = 49151");
+
+    let err = Config::from_source(&source, "APP").unwrap_err();
+    assert_eq!(err.field().unwrap().typ, "u16");
+    assert_eq!(
+        err.to_string(),
+        "Configuration from environment variables failed. `APP_PORT` of `Config.port` violates \
+         `max = 49151`, expected u16, must be at most 49151"
+    );
+}
+
+#[test]
+fn test_validate_len() {
+    let source = MapSource::new([("APP_NAME", "")]);
+    let (var_name, rule, message, _) = validation_error(&source);
+    assert_eq!(var_name, "APP_NAME");
+    assert_eq!(rule, "len 1..=8");
+    assert_eq!(message, "length 0 must be 1..=8");
+}
+
+#[test]
+fn test_validate_one_of_regex() {
+    let source = MapSource::new([("APP_NAME", "api"), ("APP_LOG_LEVEL", "trace")]);
+    let (_, rule, message, _) = validation_error(&source);
+    assert_eq!(rule, "one of: debug, info, warn");
+    assert_eq!(message, "must be one of: debug, info, warn");
+
+    let source = MapSource::new([("APP_NAME", "api"), ("APP_URL", "example.com")]);
+    let (var_name, rule, _, _) = validation_error(&source);
+    assert_eq!(var_name, "APP_URL");
+    assert_eq!(rule, "regex = ^[a-z]+://");
+}
+
+#[test]
+fn test_validate_with() {
+    let source = MapSource::new([("APP_NAME", "api"), ("APP_WORKERS", "3")]);
+    let err = Config::from_source(&source, "APP").unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Configuration from environment variables failed. `APP_WORKERS` of `Config.workers` violates `validate = check_even`, expected u32, 3 is not even"
+    );
+    assert_eq!(err.kind(), "validation_error");
+}
+
+#[test]
+fn test_validate_all_errors() {
+    let source = MapSource::new([
+        ("APP_PORT", "80"),
+        ("APP_NAME", "too long name"),
+        ("APP_PIN", "1"),
+    ]);
+    let errors = Config::from_source_all_errors(&source, "APP").unwrap_err();
+    let var_names: Vec<_> = errors
+        .iter()
+        .map(|err| match err {
+            EnvStructError::ValidationError { var_name, .. } => var_name.as_str(),
+            err => panic!("unexpected error: {err}"),
+        })
+        .collect();
+    assert_eq!(var_names, vec!["APP_PORT", "APP_NAME", "APP_PIN"]);
+}
+
+#[test]
+fn test_validate_usage() {
+    let entries = Config::get_env_entries("APP", None).unwrap();
+    assert_eq!(entries[0].constraints, vec!["min = 1024", "max = 49151"]);
+    assert_eq!(entries[1].constraints, vec!["len 1..=8"]);
+    assert_eq!(entries[4].constraints, vec!["validate = check_even"]);
+
+    let usage = Config::usage_with_prefix("APP").unwrap();
+    println!("{usage}");
+    assert!(usage.contains("Constraints: min = 1024; max = 49151"));
+    assert!(usage.contains("Constraints: one of: debug, info, warn"));
+}
@@ -0,0 +1,84 @@
+#![allow(dead_code)]
+use envstruct::prelude::*;
+
+#[derive(EnvStruct, Debug)]
+pub struct Config {
+    pub db: DB,
+    pub tls: Tls,
+}
+
+#[derive(EnvStruct, Debug)]
+#[env(validate_with = Self::check)]
+pub struct DB {
+    #[env(default = 1)]
+    pub min_pool: u32,
+    #[env(default = 10)]
+    pub max_pool: u32,
+}
+
+impl DB {
+    fn check(&self) -> Result<(), String> {
+        match self.min_pool <= self.max_pool {
+            true => Ok(()),
+            false => Err("MIN_POOL must not exceed MAX_POOL".to_string()),
+        }
+    }
+}
+
+#[derive(EnvStruct, Debug)]
+#[env(validate_with = check_tls)]
+pub struct Tls {
+    pub cert: Option<String>,
+    pub key: Option<String>,
+}
+
+fn check_tls(tls: &Tls) -> Result<(), &'static str> {
+    match (&tls.cert, &tls.key) {
+        (Some(_), None) | (None, Some(_)) => Err("CERT and KEY must both be set"),
+        _ => Ok(()),
+    }
+}
+
+#[test]
+fn test_validate_with() {
+    let source = MapSource::new([("APP_TLS_CERT", "cert.pem"), ("APP_TLS_KEY", "key.pem")]);
+    let config = Config::from_source(&source, "APP").unwrap();
+    assert_eq!(config.db.max_pool, 10);
+    assert_eq!(config.tls.key.as_deref(), Some("key.pem"));
+}
+
+#[test]
+fn test_validate_with_errors() {
+    let source = MapSource::new([("APP_DB_MIN_POOL", "20")]);
+    let err = Config::from_source(&source, "APP").unwrap_err();
+    assert!(matches!(
+        err,
+        EnvStructError::ValidationError { ref var_name, ref rule, ref field, .. }
+            if var_name == "APP_DB" && rule == "validate_with = Self::check" && field.path == "Config.db"
+    ));
+    assert_eq!(
+        err.to_string(),
+        "Configuration from environment variables failed. `APP_DB` of `Config.db` violates \
+         `validate_with = Self::check`, expected DB, MIN_POOL must not exceed MAX_POOL"
+    );
+
+    let source = MapSource::new([("APP_DB_MIN_POOL", "20"), ("APP_TLS_KEY", "key.pem")]);
+    let errors = Config::from_source_all_errors(&source, "APP").unwrap_err();
+    let var_names: Vec<_> = errors
+        .iter()
+        .filter_map(|err| match err {
+            EnvStructError::ValidationError { var_name, .. } => Some(var_name.as_str()),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(var_names, vec!["APP_DB", "APP_TLS"]);
+}
+
+#[test]
+fn test_validate_with_field_errors_first() {
+    // the struct is only validated once all of its fields parsed
+    let source = MapSource::new([("APP_DB_MIN_POOL", "many"), ("APP_TLS_KEY", "key.pem")]);
+    let errors = Config::from_source_all_errors(&source, "APP").unwrap_err();
+    let kinds: Vec<_> = errors.iter().map(EnvStructError::kind).collect();
+    assert_eq!(kinds, vec!["parse_error", "validation_error"]);
+}
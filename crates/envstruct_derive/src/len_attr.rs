@@ -0,0 +1,8 @@
+/// Represents the bounds of the `len` validation, e.g. `len(min = 1, max = 64)`.
+#[derive(Debug, darling::FromMeta)]
+pub struct LenAttr {
+    /// The smallest valid length.
+    pub min: Option<usize>,
+    /// The largest valid length.
+    pub max: Option<usize>,
+}
@@ -1,47 +1,329 @@
 mod default_attr;
+mod len_attr;
 mod normalize_type_path;
+mod rename_all;
+mod required_if_attr;
 
-use darling::{ast, FromDeriveInput, FromField};
+use darling::{
+    ast,
+    util::{Flag, SpannedValue},
+    FromDeriveInput, FromField, FromVariant,
+};
 use default_attr::*;
+use len_attr::*;
 use normalize_type_path::*;
 use proc_macro::TokenStream;
 use quote::*;
+use rename_all::*;
+use required_if_attr::*;
 use syn::spanned::Spanned;
 
 /// Derives the `EnvStruct` trait for a struct or enum.
 #[proc_macro_derive(EnvStruct, attributes(env))]
 pub fn derive(input: TokenStream) -> TokenStream {
-    let derive_input: syn::DeriveInput = syn::parse(input).expect("Failed to parse derive input");
-    let receiver = EnvStructInputReceiver::from_derive_input(&derive_input)
-        .expect("Failed to parse input for darling receiver");
-    quote!(#receiver).into()
+    let derive_input = syn::parse_macro_input!(input as syn::DeriveInput);
+    match EnvStructInputReceiver::from_derive_input(&derive_input) {
+        Ok(receiver) => quote!(#receiver).into(),
+        Err(err) => err.write_errors().into(),
+    }
 }
 
 /// Receiver for the `EnvStruct` derive input.
 #[derive(Debug, FromDeriveInput)]
-#[darling(attributes(EnvStruct), supports(any))]
+#[darling(attributes(env), supports(any), and_then = Self::validate)]
 struct EnvStructInputReceiver {
     ident: syn::Ident,
     generics: syn::Generics,
-    data: ast::Data<(), EnvStructFieldReceiver>,
+    data: ast::Data<EnvStructVariantReceiver, EnvStructFieldReceiver>,
+    file_fallback: Flag,
+    rename_all: Option<SpannedValue<RenameAll>>,
+    separator: Option<SpannedValue<String>>,
+    prefix: Option<SpannedValue<String>>,
+    validate_with: Option<syn::Path>,
+    tag: Option<SpannedValue<String>>,
+    case_sensitive: Flag,
+    from_str: Flag,
+    debug: Flag,
+}
+
+/// Receiver for the variants of an `EnvStruct` enum.
+#[derive(Debug, FromVariant)]
+#[darling(attributes(env), and_then = Self::validate)]
+struct EnvStructVariantReceiver {
+    ident: syn::Ident,
+    fields: ast::Fields<EnvStructFieldReceiver>,
+    rename: Option<String>,
+    #[darling(multiple, rename = "alias")]
+    aliases: Vec<String>,
+}
+
+impl EnvStructVariantReceiver {
+    /// Reads the field of a newtype variant under the variant name, like a flattened field.
+    fn validate(mut self) -> darling::Result<Self> {
+        if let [field] = self.fields.fields.as_mut_slice() {
+            if field.ident.is_none() && field.name.is_none() {
+                field.flatten = Flag::present();
+            }
+        }
+        Ok(self)
+    }
+
+    /// Returns the value selecting the variant, the `rename` attribute or the variant name in
+    /// snake case, e.g. `file_system`.
+    fn value(&self) -> String {
+        self.rename
+            .clone()
+            .unwrap_or_else(|| to_snake_case(&self.ident.to_string()))
+    }
 }
 
 /// Receiver for the fields of the `EnvStruct`.
 #[derive(Debug, FromField)]
-#[darling(attributes(env))]
+#[darling(attributes(env), forward_attrs(doc), and_then = Self::validate)]
 struct EnvStructFieldReceiver {
     ident: Option<syn::Ident>,
     ty: syn::Type,
+    attrs: Vec<syn::Attribute>,
     name: Option<String>,
+    help: Option<String>,
     default: Option<DefaultAttr>,
     with: Option<syn::Expr>,
+    flatten: Flag,
+    skip: Flag,
     #[darling(default)]
-    flatten: bool,
+    file_fallback: bool,
     #[darling(default)]
-    skip: bool,
+    secret: bool,
+    #[darling(multiple, rename = "alias")]
+    aliases: Vec<String>,
+    deprecated: Option<String>,
+    validate: Option<syn::Path>,
+    min: Option<syn::Expr>,
+    max: Option<syn::Expr>,
+    len: Option<LenAttr>,
+    regex: Option<String>,
+    one_of: Option<Vec<syn::LitStr>>,
+    #[darling(multiple)]
+    required_if: Vec<RequiredIfAttr>,
+    #[darling(multiple)]
+    conflicts_with: Vec<String>,
+    group: Option<String>,
+    exactly_one: Flag,
 }
 
+/// Types parsed from a single variable, flattening them would read the prefix itself.
+const PRIMITIVE_TYPES: &[&str] = &[
+    "bool", "char", "str", "String", "PathBuf", "u8", "u16", "u32", "u64", "u128", "usize", "i8",
+    "i16", "i32", "i64", "i128", "isize", "f32", "f64",
+];
+
 impl EnvStructFieldReceiver {
+    /// Rejects combinations of attributes that have no meaning.
+    fn validate(self) -> darling::Result<Self> {
+        let mut errors = darling::Error::accumulator();
+
+        if self.skip.is_present() {
+            let conflicts = [
+                ("default", self.default.is_some()),
+                ("name", self.name.is_some()),
+                ("with", self.with.is_some()),
+                ("flatten", self.flatten.is_present()),
+                ("alias", !self.aliases.is_empty()),
+                ("deprecated", self.deprecated.is_some()),
+                ("validate", self.has_validations()),
+                ("required_if", !self.required_if.is_empty()),
+                ("conflicts_with", !self.conflicts_with.is_empty()),
+                ("group", self.group.is_some()),
+            ];
+            for (attr, _) in conflicts.iter().filter(|(_, present)| *present) {
+                errors.push(
+                    darling::Error::custom(format!(
+                        "`skip` cannot be combined with `{attr}`, skipped fields are not parsed"
+                    ))
+                    .with_span(&self.skip.span()),
+                );
+            }
+        }
+
+        if self.flatten.is_present() && (!self.aliases.is_empty() || self.deprecated.is_some()) {
+            errors.push(
+                darling::Error::custom(
+                    "`alias` and `deprecated` cannot be combined with `flatten`, \
+                     they apply to the variable of a field",
+                )
+                .with_span(&self.flatten.span()),
+            );
+        }
+
+        if self.has_relations() && !self.is_optional() {
+            errors.push(
+                darling::Error::custom(
+                    "`required_if`, `conflicts_with` and `group` need an `Option` field, \
+                     other fields are always set",
+                )
+                .with_span(&self.ty),
+            );
+        }
+
+        if self.exactly_one.is_present() && self.group.is_none() {
+            errors.push(
+                darling::Error::custom("`exactly_one` needs a `group`")
+                    .with_span(&self.exactly_one.span()),
+            );
+        }
+
+        if self.flatten.is_present() && self.has_constraints() {
+            errors.push(
+                darling::Error::custom(
+                    "`min`, `max`, `len`, `regex` and `one_of` cannot be combined with `flatten`, \
+                     use `validate` to check a nested struct",
+                )
+                .with_span(&self.flatten.span()),
+            );
+        }
+
+        if self.flatten.is_present() {
+            if let Some(primitive) = primitive_type_name(&self.ty) {
+                errors.push(
+                    darling::Error::custom(format!(
+                        "`flatten` cannot be used on the primitive type `{primitive}`, \
+                         it is only supported for nested structs"
+                    ))
+                    .with_span(&self.flatten.span()),
+                );
+            }
+        }
+
+        errors.finish_with(self)
+    }
+
+    /// Returns whether the field has relations to other fields.
+    fn has_relations(&self) -> bool {
+        !self.required_if.is_empty() || !self.conflicts_with.is_empty() || self.group.is_some()
+    }
+
+    /// Returns whether the field is an `Option`, which is not set if it is `None`.
+    fn is_optional(&self) -> bool {
+        self.with.is_none() && is_option(&self.ty)
+    }
+
+    /// Generates a token stream for whether the field of the parsed `value` is set.
+    fn is_set_expr(&self) -> proc_macro2::TokenStream {
+        let ident = &self.ident;
+        match self.is_optional() {
+            true => quote!(value.#ident.is_some()),
+            false => quote!(true),
+        }
+    }
+
+    /// Generates a token stream for the field of the parsed `value` as an optional string.
+    fn value_string_expr(&self) -> proc_macro2::TokenStream {
+        let ident = &self.ident;
+        match self.is_optional() {
+            true => quote!(value.#ident.as_ref().map(|value| value.to_string())),
+            false => quote!(Some(value.#ident.to_string())),
+        }
+    }
+
+    /// Returns whether the field has declarative constraints.
+    fn has_constraints(&self) -> bool {
+        self.min.is_some()
+            || self.max.is_some()
+            || self.len.is_some()
+            || self.regex.is_some()
+            || self.one_of.is_some()
+    }
+
+    /// Returns whether the parsed value of the field is validated.
+    fn has_validations(&self) -> bool {
+        self.validate.is_some() || self.has_constraints()
+    }
+
+    /// Generates token streams for the descriptions of the constraints, listed in the usage.
+    pub fn constraint_exprs(&self) -> Vec<proc_macro2::TokenStream> {
+        let mut exprs = Vec::new();
+        if let Some(min) = &self.min {
+            exprs.push(quote!(format!("min = {}", #min)));
+        }
+        if let Some(max) = &self.max {
+            exprs.push(quote!(format!("max = {}", #max)));
+        }
+        if let Some(LenAttr { min, max }) = &self.len {
+            let (min, max) = (option_expr(min), option_expr(max));
+            exprs.push(quote!(::envstruct::len_rule(#min, #max)));
+        }
+        if let Some(regex) = &self.regex {
+            exprs.push(quote!(format!("regex = {}", #regex)));
+        }
+        if let Some(one_of) = &self.one_of {
+            let accepted = one_of
+                .iter()
+                .map(syn::LitStr::value)
+                .collect::<Vec<_>>()
+                .join(", ");
+            exprs.push(quote!(format!("one of: {}", #accepted)));
+        }
+        if let Some(validate) = &self.validate {
+            let rule = format!("validate = {}", path_string(validate));
+            exprs.push(quote!(#rule.to_string()));
+        }
+        exprs
+    }
+
+    /// Generates a token stream validating the parsed `value`, an `Option` is validated if it is
+    /// `Some`. The variable name is bound to `var_name`.
+    pub fn validation_expr(&self) -> Option<proc_macro2::TokenStream> {
+        if !self.has_validations() {
+            return None;
+        }
+
+        let mut checks = Vec::new();
+        if let Some(min) = &self.min {
+            checks.push(quote!(::envstruct::check_min(&var_name, value, #min)?;));
+        }
+        if let Some(max) = &self.max {
+            checks.push(quote!(::envstruct::check_max(&var_name, value, #max)?;));
+        }
+        if let Some(LenAttr { min, max }) = &self.len {
+            let (min, max) = (option_expr(min), option_expr(max));
+            checks.push(quote!(::envstruct::check_len(&var_name, value.len(), #min, #max)?;));
+        }
+        if let Some(regex) = &self.regex {
+            checks.push(quote!(::envstruct::check_regex(&var_name, value, #regex)?;));
+        }
+        if let Some(one_of) = &self.one_of {
+            checks.push(quote!(::envstruct::check_one_of(&var_name, value, &[#( #one_of ),*])?;));
+        }
+        if let Some(validate) = &self.validate {
+            let rule = format!("validate = {}", path_string(validate));
+            checks.push(quote!(::envstruct::check_with(&var_name, #rule, #validate(value))?;));
+        }
+
+        let checks = quote! {
+            ::envstruct::check_value(value, |value| {
+                #( #checks )*
+                Ok(())
+            })?;
+        };
+        let checks = match self.is_optional() {
+            true => quote! {
+                if let Some(value) = &parsed {
+                    #checks
+                }
+            },
+            false => quote! {
+                let value = &parsed;
+                #checks
+            },
+        };
+        Some(quote! {
+            .and_then(|parsed| {
+                #checks
+                Ok(parsed)
+            })
+        })
+    }
+
     /// Generates a token stream for the field name or index.
     pub fn name_exr(&self, index: usize) -> proc_macro2::TokenStream {
         self.ident
@@ -83,6 +365,95 @@ impl EnvStructFieldReceiver {
             .unwrap_or_else(|| quote!(None))
     }
 
+    /// Generates a token stream for the source the field is parsed from.
+    pub fn source_expr(&self, file_fallback: bool) -> proc_macro2::TokenStream {
+        if self.file_fallback || file_fallback {
+            quote!(&::envstruct::FileFallback(source))
+        } else {
+            quote!(source)
+        }
+    }
+
+    /// Returns the description of the field, the `help` attribute or the doc comment.
+    pub fn description(&self) -> Option<String> {
+        if self.help.is_some() {
+            return self.help.clone();
+        }
+
+        let lines: Vec<String> = self
+            .attrs
+            .iter()
+            .filter_map(|attr| match &attr.meta {
+                syn::Meta::NameValue(syn::MetaNameValue {
+                    value:
+                        syn::Expr::Lit(syn::ExprLit {
+                            lit: syn::Lit::Str(doc),
+                            ..
+                        }),
+                    ..
+                }) if attr.path().is_ident("doc") => Some(doc.value().trim().to_string()),
+                _ => None,
+            })
+            .collect();
+
+        // join the lines of a paragraph, keep paragraphs on separate lines
+        let description = lines
+            .split(|line| line.is_empty())
+            .filter(|paragraph| !paragraph.is_empty())
+            .map(|paragraph| paragraph.join(" "))
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        (!description.is_empty()).then_some(description)
+    }
+
+    /// Generates token streams for the names of the aliases, if any.
+    pub fn alias_exprs(&self) -> Option<Vec<proc_macro2::TokenStream>> {
+        (!self.aliases.is_empty()).then(|| {
+            self.aliases
+                .iter()
+                .map(|alias| quote!(naming.join(&prefix, #alias)))
+                .collect()
+        })
+    }
+
+    /// Generates a token stream for the name the field is read from, resolving aliases and
+    /// reporting deprecated variables to the source.
+    pub fn resolved_var_name_expr(
+        &self,
+        source_expr: &proc_macro2::TokenStream,
+    ) -> proc_macro2::TokenStream {
+        let var_name_expr = self.var_name_expr();
+        if self.aliases.is_empty() && self.deprecated.is_none() {
+            return var_name_expr;
+        }
+
+        let aliases = self.alias_exprs().unwrap_or_default();
+        let deprecated = match &self.deprecated {
+            Some(message) => quote!(Some(#message)),
+            None => quote!(None),
+        };
+        quote! {
+            ::envstruct::resolve_env_name(#source_expr, #var_name_expr, &[#( #aliases ),*], #deprecated)
+        }
+    }
+
+    /// Returns the name of the method to call and the naming argument, `with` types take no
+    /// naming policy.
+    pub fn naming_call(
+        &self,
+        method: &str,
+    ) -> (proc_macro2::Ident, Option<proc_macro2::TokenStream>) {
+        let span = self.ty.span();
+        match self.with {
+            Some(_) => (format_ident!("{}", method, span = span), None),
+            None => (
+                format_ident!("{}_named", method, span = span),
+                Some(quote!(, &naming)),
+            ),
+        }
+    }
+
     /// Generates a token stream for the environment variable name.
     pub fn var_name_expr(&self) -> proc_macro2::TokenStream {
         let var_name = self.name.clone().unwrap_or_else(|| {
@@ -92,11 +463,570 @@ impl EnvStructFieldReceiver {
                 .unwrap_or_default()
         });
 
-        if self.flatten {
+        if self.flatten.is_present() {
             quote!(&prefix)
+        } else if self.name.is_none() && self.with.is_none() && self.ident.is_some() {
+            // a nested struct may declare a prefix replacing the field name
+            let field_type = self.type_expr();
+            quote_spanned! {self.ty.span() =>
+                naming.join(&prefix, #field_type::env_prefix().unwrap_or(#var_name))
+            }
         } else {
-            quote!(::envstruct::concat_env_name(&prefix, #var_name))
+            quote!(naming.join(&prefix, #var_name))
+        }
+    }
+}
+
+impl EnvStructInputReceiver {
+    /// Rejects relations to fields that do not exist and relations on the fields of variants,
+    /// and attributes that do not apply to the kind of the type.
+    fn validate(self) -> darling::Result<Self> {
+        let mut errors = darling::Error::accumulator();
+
+        if self.is_unit_enum() {
+            // an enum of unit variants is parsed from a single variable like a primitive
+            let ignored = [
+                (
+                    "validate_with",
+                    self.validate_with.as_ref().map(Spanned::span),
+                ),
+                ("prefix", self.prefix.as_ref().map(SpannedValue::span)),
+                (
+                    "rename_all",
+                    self.rename_all.as_ref().map(SpannedValue::span),
+                ),
+                ("separator", self.separator.as_ref().map(SpannedValue::span)),
+                (
+                    "file_fallback",
+                    self.file_fallback
+                        .is_present()
+                        .then(|| self.file_fallback.span()),
+                ),
+                ("tag", self.tag.as_ref().map(SpannedValue::span)),
+            ];
+            for (attr, span) in ignored {
+                if let Some(span) = span {
+                    errors.push(
+                        darling::Error::custom(format!(
+                            "`{attr}` is not supported on enums of unit variants, they are parsed from a single variable"
+                        ))
+                        .with_span(&span),
+                    );
+                }
+            }
+        } else if self.from_str.is_present() {
+            errors.push(
+                darling::Error::custom("`from_str` is only supported on enums of unit variants")
+                    .with_span(&self.from_str.span()),
+            );
+        }
+
+        if self.case_sensitive.is_present() && matches!(self.data, ast::Data::Struct(_)) {
+            errors.push(
+                darling::Error::custom("`case_sensitive` is only supported on enums")
+                    .with_span(&self.case_sensitive.span()),
+            );
+        }
+
+        if self.debug.is_present() && matches!(self.data, ast::Data::Enum(_)) {
+            errors.push(
+                darling::Error::custom("`debug` is only supported on structs")
+                    .with_span(&self.debug.span()),
+            );
+        }
+
+        if let ast::Data::Enum(variants) = &self.data {
+            for field in variants.iter().flat_map(|variant| variant.fields.iter()) {
+                if field.has_relations() {
+                    errors.push(
+                        darling::Error::custom(
+                            "`required_if`, `conflicts_with` and `group` are not supported on the fields of enum variants",
+                        )
+                        .with_span(&field.ty),
+                    );
+                }
+            }
+        }
+
+        for field in self.fields() {
+            let unknown = |other: &str| {
+                darling::Error::custom(format!("unknown field `{other}`")).with_span(&field.ident)
+            };
+            for RequiredIfAttr { field: other, .. } in &field.required_if {
+                if self.field_named(other).is_none() {
+                    errors.push(unknown(other));
+                }
+            }
+            for other in &field.conflicts_with {
+                match self.field_named(other) {
+                    Some(other_field) if !other_field.is_optional() => errors.push(
+                        darling::Error::custom(format!(
+                            "`conflicts_with` needs an `Option` field, `{other}` is always set"
+                        ))
+                        .with_span(&field.ident),
+                    ),
+                    Some(_) => {}
+                    None => errors.push(unknown(other)),
+                }
+            }
+        }
+
+        errors.finish_with(self)
+    }
+
+    /// Returns whether the type is an enum of unit variants, parsed from a single variable.
+    fn is_unit_enum(&self) -> bool {
+        match &self.data {
+            ast::Data::Enum(variants) => variants.iter().all(|variant| variant.fields.is_unit()),
+            ast::Data::Struct(_) => false,
+        }
+    }
+
+    /// Returns the parsed fields of a struct.
+    fn fields(&self) -> Vec<&EnvStructFieldReceiver> {
+        match &self.data {
+            ast::Data::Struct(fields) => fields
+                .iter()
+                .filter(|field| !field.skip.is_present())
+                .collect(),
+            ast::Data::Enum(_) => Vec::new(),
+        }
+    }
+
+    /// Generates a `Debug` impl printing [`REDACTED`] for the `secret` fields, for
+    /// `#[env(debug)]`.
+    ///
+    /// [`REDACTED`]: envstruct::REDACTED
+    fn debug_impl(&self) -> Option<proc_macro2::TokenStream> {
+        let ast::Data::Struct(fields) = &self.data else {
+            return None;
+        };
+        if !self.debug.is_present() {
+            return None;
+        }
+        let EnvStructInputReceiver {
+            ident, generics, ..
+        } = self;
+        let (imp, ty, where_clause) = generics.split_for_impl();
+        let name = ident.to_string();
+        let values: Vec<_> = fields
+            .iter()
+            .enumerate()
+            .map(|(index, field)| {
+                let member = match &field.ident {
+                    Some(ident) => quote!(#ident),
+                    None => syn::Index::from(index).to_token_stream(),
+                };
+                match field.secret {
+                    true => quote!(&format_args!("{}", ::envstruct::REDACTED)),
+                    false => quote!(&self.#member),
+                }
+            })
+            .collect();
+        let body = match fields.style {
+            ast::Style::Struct => {
+                let names = fields
+                    .iter()
+                    .map(|field| field.ident.as_ref().map(ToString::to_string));
+                quote!(f.debug_struct(#name) #(.field(#names, #values))* .finish())
+            }
+            ast::Style::Tuple => quote!(f.debug_tuple(#name) #(.field(#values))* .finish()),
+            ast::Style::Unit => quote!(f.write_str(#name)),
+        };
+        Some(quote! {
+            impl #imp ::std::fmt::Debug for #ident #ty #where_clause {
+                fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                    #body
+                }
+            }
+        })
+    }
+
+    /// Returns the parsed field with the given name.
+    fn field_named(&self, name: &str) -> Option<&EnvStructFieldReceiver> {
+        self.fields()
+            .into_iter()
+            .find(|field| field.ident.as_ref().is_some_and(|ident| ident == name))
+    }
+
+    /// Returns the groups of the fields in order of appearance, with their members and whether
+    /// one member must be set.
+    fn groups(&self) -> Vec<(&str, Vec<&EnvStructFieldReceiver>, bool)> {
+        let mut groups: Vec<(&str, Vec<&EnvStructFieldReceiver>, bool)> = Vec::new();
+        for field in self.fields() {
+            let Some(group) = field.group.as_deref() else {
+                continue;
+            };
+            let exactly_one = field.exactly_one.is_present();
+            match groups.iter_mut().find(|(name, _, _)| *name == group) {
+                Some((_, members, required)) => {
+                    members.push(field);
+                    *required |= exactly_one;
+                }
+                None => groups.push((group, vec![field], exactly_one)),
+            }
+        }
+        groups
+    }
+
+    /// Generates token streams checking the relations between the fields of the parsed `value`.
+    fn relation_checks(&self) -> Vec<proc_macro2::TokenStream> {
+        let mut checks = Vec::new();
+        for field in self.fields() {
+            let var_name_expr = field.var_name_expr();
+            let is_set_expr = field.is_set_expr();
+            for RequiredIfAttr {
+                field: other,
+                value,
+            } in &field.required_if
+            {
+                let Some(other) = self.field_named(other) else {
+                    continue;
+                };
+                let other_var_name_expr = other.var_name_expr();
+                let other_value_expr = other.value_string_expr();
+                checks.push(quote! {
+                    ::envstruct::check_required_if(&#var_name_expr, #is_set_expr, &#other_var_name_expr, #other_value_expr, #value)
+                });
+            }
+            for other in &field.conflicts_with {
+                let Some(other) = self.field_named(other) else {
+                    continue;
+                };
+                let other_var_name_expr = other.var_name_expr();
+                let other_is_set_expr = other.is_set_expr();
+                checks.push(quote! {
+                    ::envstruct::check_conflicts_with(&#var_name_expr, #is_set_expr, &#other_var_name_expr, #other_is_set_expr)
+                });
+            }
+        }
+        for (group, members, exactly_one) in self.groups() {
+            let members = members.iter().map(|member| {
+                let var_name_expr = member.var_name_expr();
+                let is_set_expr = member.is_set_expr();
+                quote!((#var_name_expr, #is_set_expr))
+            });
+            checks.push(quote! {
+                ::envstruct::check_group(#group, &[#( #members ),*], #exactly_one)
+            });
+        }
+        checks
+    }
+
+    /// Generates token streams describing the relations of a field in the usage.
+    fn relation_notes(&self, field: &EnvStructFieldReceiver) -> Vec<proc_macro2::TokenStream> {
+        let mut notes = Vec::new();
+        for RequiredIfAttr {
+            field: other,
+            value,
+        } in &field.required_if
+        {
+            if let Some(other) = self.field_named(other) {
+                let other_var_name_expr = other.var_name_expr();
+                notes.push(quote!(
+                    format!("required if `{}` is `{}`", #other_var_name_expr, #value)
+                ));
+            }
+        }
+        for other in &field.conflicts_with {
+            if let Some(other) = self.field_named(other) {
+                let other_var_name_expr = other.var_name_expr();
+                notes.push(quote!(format!("conflicts with `{}`", #other_var_name_expr)));
+            }
+        }
+        if let Some(group) = &field.group {
+            if let Some((_, members, exactly_one)) =
+                self.groups().into_iter().find(|(name, _, _)| name == group)
+            {
+                let members = members.iter().map(|member| member.var_name_expr());
+                notes.push(
+                    quote!(::envstruct::group_rule(#group, &[#( #members ),*], #exactly_one)),
+                );
+            }
         }
+        notes
+    }
+
+    /// Generates a token stream parsing the fields into the struct or variant `constructor`,
+    /// evaluating to a `Result`. The fields are read under `prefix`.
+    fn parse_fields_expr(
+        &self,
+        constructor: proc_macro2::TokenStream,
+        fields: &[EnvStructFieldReceiver],
+        relation_expr: Option<proc_macro2::TokenStream>,
+    ) -> proc_macro2::TokenStream {
+        let parsed_fields: Vec<_> = fields
+            .iter()
+            .enumerate()
+            .filter(|(_, field)| !field.skip.is_present())
+            .map(|(index, field)| (format_ident!("__field{}", index), field))
+            .collect();
+
+        let parse_exprs: Vec<_> = parsed_fields
+            .iter()
+            .map(|(binding, field)| {
+                let index = binding.to_string().trim_start_matches("__field").to_string();
+                let field_path = field
+                    .ident
+                    .as_ref()
+                    .map(|ident| ident.to_string())
+                    .unwrap_or(index);
+                let field_type = field.type_expr();
+                let var_default = field.default_expr();
+                let source_expr = field.source_expr(self.file_fallback.is_present());
+                let var_name_expr = field.resolved_var_name_expr(&source_expr);
+                let (parse_fn, naming_arg) = field.naming_call("parse_from_source");
+                let redact_expr = field
+                    .secret
+                    .then(|| quote!(.map_err(::envstruct::EnvStructError::redact)));
+
+                match field.validation_expr() {
+                    Some(validation_expr) => quote_spanned! {field.ty.span() =>
+                        let #binding = {
+                            let var_name = #var_name_expr;
+                            errors.collect(#field_type::#parse_fn(#source_expr, &var_name, #var_default #naming_arg) #validation_expr #redact_expr.map_err(|err| err.in_field(#field_path)))
+                        };
+                    },
+                    None => quote_spanned! {field.ty.span() =>
+                        let #binding = errors.collect(#field_type::#parse_fn(#source_expr, #var_name_expr, #var_default #naming_arg) #redact_expr.map_err(|err| err.in_field(#field_path)));
+                    },
+                }
+            })
+            .collect();
+
+        let bindings: Vec<_> = parsed_fields.iter().map(|(binding, _)| binding).collect();
+
+        let field_exprs: Vec<_> = fields
+            .iter()
+            .enumerate()
+            .map(|(index, field)| {
+                let field_name = field.name_exr(index);
+
+                if field.skip.is_present() {
+                    quote_spanned! {field.ty.span() =>
+                        #field_name: Default::default()
+                    }
+                } else {
+                    let binding = format_ident!("__field{}", index);
+                    quote_spanned! {field.ty.span() =>
+                        #field_name: #binding.into()
+                    }
+                }
+            })
+            .collect();
+
+        quote! {{
+            let mut errors = ::envstruct::EnvStructErrors::new();
+            #( #parse_exprs )*
+            match (#( #bindings, )*) {
+                (#( Some(#bindings), )*) => {
+                    let value = #constructor {
+                        #( #field_exprs, )*
+                    };
+                    #relation_expr
+                    Ok(value)
+                }
+                _ => Err(errors.into()),
+            }
+        }}
+    }
+
+    /// Generates a token stream collecting the entries of the fields read under `prefix`.
+    fn entries_expr(&self, fields: &[EnvStructFieldReceiver]) -> proc_macro2::TokenStream {
+        let inspect_exprs: Vec<_> = fields
+            .iter()
+            .filter(|field| !field.skip.is_present())
+            .map(|field| {
+                let field_type = field.type_expr();
+                let var_default = field.default_expr();
+                let var_name_expr = field.var_name_expr();
+
+                let (entries_fn, naming_arg) = field.naming_call("get_env_entries");
+                let secret_expr = field.secret.then(|| quote!(entry.secret = true;));
+                let file_fallback_expr = (field.file_fallback || self.file_fallback.is_present())
+                    .then(|| quote!(entry.file_fallback = true;));
+                let description_expr = field.description().map(|description| {
+                    quote! {
+                        entry.description = entry.description.or_else(|| Some(#description.to_string()));
+                    }
+                });
+                let alias_expr = field.alias_exprs().map(|aliases| {
+                    quote!(entry.aliases = vec![#( #aliases ),*];)
+                });
+                let deprecated_expr = field.deprecated.as_ref().map(|message| {
+                    quote!(entry.deprecated = Some(#message.to_string());)
+                });
+                let mut constraint_exprs = field.constraint_exprs();
+                constraint_exprs.extend(self.relation_notes(field));
+                let constraints_expr = (!constraint_exprs.is_empty()).then(|| {
+                    quote!(entry.constraints = vec![#( #constraint_exprs ),*];)
+                });
+
+                if secret_expr.is_some()
+                    || file_fallback_expr.is_some()
+                    || description_expr.is_some()
+                    || alias_expr.is_some()
+                    || deprecated_expr.is_some()
+                    || constraints_expr.is_some()
+                {
+                    quote_spanned! {field.ty.span() =>
+                        #field_type::#entries_fn(#var_name_expr, #var_default #naming_arg)?
+                            .into_iter()
+                            .map(|mut entry| {
+                                #secret_expr
+                                #file_fallback_expr
+                                #description_expr
+                                #alias_expr
+                                #deprecated_expr
+                                #constraints_expr
+                                entry
+                            })
+                            .collect::<Vec<_>>()
+                    }
+                } else {
+                    quote_spanned! {field.ty.span() =>
+                        #field_type::#entries_fn(#var_name_expr, #var_default #naming_arg)?
+                    }
+                }
+            })
+            .collect();
+
+        quote! {{
+            let entries: Vec<Vec<::envstruct::EnvEntry>> = vec![#( #inspect_exprs, )*];
+            entries.into_iter().flatten().collect::<Vec<_>>()
+        }}
+    }
+
+    /// Generates the bodies of `parse_from_source_named` and `get_env_entries_named` of a
+    /// struct.
+    fn struct_bodies(
+        &self,
+        fields: &[EnvStructFieldReceiver],
+    ) -> (proc_macro2::TokenStream, proc_macro2::TokenStream) {
+        let relation_checks = self.relation_checks();
+        let relation_expr = (!relation_checks.is_empty()).then(|| {
+            quote! {
+                #( errors.collect(#relation_checks); )*
+                if !errors.is_empty() {
+                    return Err(errors.into());
+                }
+            }
+        });
+        (
+            self.parse_fields_expr(quote!(Self), fields, relation_expr),
+            self.entries_expr(fields),
+        )
+    }
+
+    /// Generates a token stream for the `envstruct::EnvVariants` accepted by an enum.
+    fn variants_expr(&self, variants: &[EnvStructVariantReceiver]) -> proc_macro2::TokenStream {
+        let names = variants.iter().map(|variant| {
+            let value = variant.value();
+            let aliases = &variant.aliases;
+            quote!(&[#value #(, #aliases )*])
+        });
+        let case_sensitive = self.case_sensitive.is_present();
+        quote! {
+            ::envstruct::EnvVariants {
+                names: &[#( #names ),*],
+                case_sensitive: #case_sensitive,
+            }
+        }
+    }
+
+    /// Generates the implementation of `EnvParsePrimitive` for an enum of unit variants, parsed
+    /// from the variant names or with `FromStr` if `from_str` is set.
+    fn unit_enum_impl(&self, variants: &[EnvStructVariantReceiver]) -> proc_macro2::TokenStream {
+        let ident = &self.ident;
+        let (imp, ty, where_clause) = self.generics.split_for_impl();
+        if self.from_str.is_present() {
+            return quote_spanned! {ty.span() =>
+                impl #imp ::envstruct::EnvParsePrimitive for #ident #ty #where_clause {
+                    fn parse(val: &str) -> std::result::Result<Self, ::envstruct::BoxError> {
+                        Ok(val.parse::<#ident>()?)
+                    }
+                }
+            };
+        }
+
+        let variants_expr = self.variants_expr(variants);
+        let arms = variants.iter().enumerate().map(|(index, variant)| {
+            let variant = &variant.ident;
+            quote!(#index => Ok(Self::#variant),)
+        });
+        quote! {
+            impl #imp ::envstruct::EnvParsePrimitive for #ident #ty #where_clause {
+                fn parse(val: &str) -> std::result::Result<Self, ::envstruct::BoxError> {
+                    match (#variants_expr).find(val)? {
+                        #( #arms )*
+                        _ => unreachable!(),
+                    }
+                }
+
+                fn hint() -> Option<String> {
+                    Some(#variants_expr.hint())
+                }
+
+                fn get_env_entries(prefix: impl AsRef<str>, default: Option<&str>) -> std::result::Result<Vec<::envstruct::EnvEntry>, ::envstruct::EnvStructError> {
+                    Ok(vec![#variants_expr.entry(prefix.as_ref(), std::any::type_name::<Self>(), default)])
+                }
+            }
+        }
+    }
+
+    /// Generates the bodies of `parse_from_source_named` and `get_env_entries_named` of an enum
+    /// whose variant is selected by a tag variable, the fields of a variant are read under the
+    /// variant name.
+    fn tagged_enum_bodies(
+        &self,
+        variants: &[EnvStructVariantReceiver],
+    ) -> (proc_macro2::TokenStream, proc_macro2::TokenStream) {
+        let tag = self.tag.as_ref().map_or("kind", |tag| tag.as_str());
+        let names: Vec<_> = variants
+            .iter()
+            .map(EnvStructVariantReceiver::value)
+            .collect();
+        let variants_expr = self.variants_expr(variants);
+
+        let parse_arms = variants.iter().enumerate().map(|(index, variant)| {
+            let name = &names[index];
+            let ident = &variant.ident;
+            let parse_expr =
+                self.parse_fields_expr(quote!(Self::#ident), &variant.fields.fields, None);
+            quote! {
+                #index => {
+                    let prefix = naming.join(&prefix, #name);
+                    let result: std::result::Result<Self, ::envstruct::EnvStructError> = #parse_expr;
+                    result.map_err(|err| err.in_field(#name))
+                }
+            }
+        });
+        let parse_body = quote! {
+            let tag_name = naming.join(&prefix, #tag);
+            match ::envstruct::parse_tag(source, &tag_name, default, std::any::type_name::<Self>(), &#variants_expr)? {
+                #( #parse_arms )*
+                _ => unreachable!(),
+            }
+        };
+
+        let variant_entries = variants.iter().enumerate().map(|(index, variant)| {
+            let name = &names[index];
+            let entries_expr = self.entries_expr(&variant.fields.fields);
+            quote! {
+                {
+                    let prefix = naming.join(&prefix, #name);
+                    entries.extend(::envstruct::variant_entries(#entries_expr, &tag_name, #name));
+                }
+            }
+        });
+        let entries_body = quote! {{
+            let tag_name = naming.join(&prefix, #tag);
+            let mut entries = vec![::envstruct::tag_entry(&tag_name, std::any::type_name::<Self>(), default, &#variants_expr)];
+            #( #variant_entries )*
+            entries
+        }};
+
+        (parse_body, entries_body)
     }
 }
 
@@ -106,73 +1036,119 @@ impl ToTokens for EnvStructInputReceiver {
             ident,
             generics,
             data,
+            rename_all,
+            separator,
+            prefix,
+            validate_with,
+            ..
         } = self;
         let (imp, ty, where_clause) = generics.split_for_impl();
 
-        let impl_block = match data {
-            ast::Data::Enum(_) => {
-                quote_spanned! {ty.span() =>
-                    impl #imp ::envstruct::EnvParsePrimitive for #ident #ty #where_clause {
-                        fn parse(val: &str) -> std::result::Result<Self, ::envstruct::BoxError> {
-                            Ok(val.parse::<#ident>()?)
-                        }
-                    }
+        let (parse_body, entries_body) = match data {
+            ast::Data::Enum(variants) if self.is_unit_enum() => {
+                tokens.extend(self.unit_enum_impl(variants));
+                return;
+            }
+            ast::Data::Enum(variants) => self.tagged_enum_bodies(variants),
+            ast::Data::Struct(fields) => self.struct_bodies(&fields.fields),
+        };
+        tokens.extend(self.debug_impl());
+
+        let case_expr = rename_all.as_ref().map(|rename_all| {
+            let case = rename_all.case_expr();
+            quote!(.with_case(#case))
+        });
+        let separator_expr = separator.as_ref().map(|separator| {
+            let separator = separator.as_str();
+            quote!(.with_separator(#separator))
+        });
+        let env_prefix_fn = prefix.as_ref().map(|prefix| {
+            let prefix = prefix.as_str();
+            quote! {
+                fn env_prefix() -> Option<&'static str> {
+                    Some(#prefix)
                 }
             }
-            ast::Data::Struct(fields) => {
-                let field_exprs: Vec<_> = fields
-                    .iter()
-                    .enumerate()
-                    .map(|(index, field)| {
-                        let field_name = field.name_exr(index);
-                        let field_type = field.type_expr();
-                        let var_default = field.default_expr();
-                        let var_name_expr = field.var_name_expr();
-
-                        if field.skip {
-                             quote_spanned! {field.ty.span() =>
-                                #field_name: Default::default()
-                            }
-                        } else {
-                            quote_spanned! {field.ty.span() =>
-                                #field_name: #field_type::parse_from_env_var(#var_name_expr, #var_default)?.into()
-                            }
-                        }
-
-                    })
-                    .collect();
-
-                let inspect_exprs: Vec<_> = fields
-                    .iter()
-                    .filter(|field| !field.skip)
-                    .map(|field| {
-                        let field_type = field.type_expr();
-                        let var_default = field.default_expr();
-                        let var_name_expr = field.var_name_expr();
-
-                        quote_spanned! {field.ty.span() =>
-                            #field_type::get_env_entries(#var_name_expr, #var_default)?
-                        }
-                    })
-                    .collect();
-
-                quote! {
-                    #[allow(clippy::useless_conversion)]
-                    impl #imp ::envstruct::EnvParseNested for #ident #ty #where_clause {
-                        fn parse_from_env_var(prefix: impl AsRef<str>, default: Option<&str>) -> std::result::Result<Self, ::envstruct::EnvStructError> {
-                            Ok(Self {
-                                #( #field_exprs, )*
-                            })
-                        }
+        });
+        let naming_expr = quote!(let naming = (*naming) #case_expr #separator_expr;);
+        let validate_with_expr = validate_with.as_ref().map(|validate_with| {
+            let rule = format!("validate_with = {}", path_string(validate_with));
+            quote! {
+                ::envstruct::check_value(&value, |value| {
+                    ::envstruct::check_with(prefix.as_ref(), #rule, #validate_with(value))
+                })?;
+            }
+        });
 
-                        fn get_env_entries(prefix: impl AsRef<str>, default: Option<&str>) -> std::result::Result<Vec<::envstruct::EnvEntry>, ::envstruct::EnvStructError> {
-                            Ok(vec![#( #inspect_exprs, )*].into_iter().flatten().collect())
-                        }
-                    }
+        tokens.extend(quote! {
+            #[allow(clippy::useless_conversion, unused_variables)]
+            impl #imp ::envstruct::EnvParseNested for #ident #ty #where_clause {
+                fn parse_from_source_named(source: &dyn ::envstruct::EnvSource, prefix: impl AsRef<str>, default: Option<&str>, naming: &::envstruct::EnvNaming) -> std::result::Result<Self, ::envstruct::EnvStructError> {
+                    #naming_expr
+                    let result: std::result::Result<Self, ::envstruct::EnvStructError> = { #parse_body };
+                    let value = result?;
+                    #validate_with_expr
+                    Ok(value)
+                }
+
+                fn get_env_entries_named(prefix: impl AsRef<str>, default: Option<&str>, naming: &::envstruct::EnvNaming) -> std::result::Result<Vec<::envstruct::EnvEntry>, ::envstruct::EnvStructError> {
+                    #naming_expr
+                    Ok(#entries_body)
                 }
+
+                #env_prefix_fn
             }
-        };
+        });
+    }
+}
+
+/// Converts a name in camel case to snake case, e.g. `FileSystem` to `file_system`.
+fn to_snake_case(name: &str) -> String {
+    let mut result = String::new();
+    let mut previous: Option<char> = None;
+    for c in name.chars() {
+        if c.is_uppercase() && previous.is_some_and(|p| p.is_lowercase() || p.is_ascii_digit()) {
+            result.push('_');
+        }
+        result.extend(c.to_lowercase());
+        previous = Some(c);
+    }
+    result
+}
+
+/// Returns whether the type is an `Option`.
+fn is_option(ty: &syn::Type) -> bool {
+    matches!(ty, syn::Type::Path(type_path)
+        if type_path.path.segments.last().is_some_and(|segment| segment.ident == "Option"))
+}
 
-        tokens.extend(impl_block);
+/// Generates a token stream for an optional value.
+fn option_expr<T: ToTokens>(value: &Option<T>) -> proc_macro2::TokenStream {
+    match value {
+        Some(value) => quote!(Some(#value)),
+        None => quote!(None),
+    }
+}
+
+/// Returns the path as written, e.g. `checks::port`.
+fn path_string(path: &syn::Path) -> String {
+    quote!(#path).to_string().replace(' ', "")
+}
+
+/// Returns the name of the type if it is a primitive, looking through `Option`.
+fn primitive_type_name(ty: &syn::Type) -> Option<String> {
+    let syn::Type::Path(type_path) = ty else {
+        return None;
+    };
+    let segment = type_path.path.segments.last()?;
+    if segment.ident == "Option" {
+        if let syn::PathArguments::AngleBracketed(args) = &segment.arguments {
+            if let Some(syn::GenericArgument::Type(inner)) = args.args.first() {
+                return primitive_type_name(inner);
+            }
+        }
+        return None;
     }
+    let name = segment.ident.to_string();
+    PRIMITIVE_TYPES.contains(&name.as_str()).then_some(name)
 }
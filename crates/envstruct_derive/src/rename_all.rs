@@ -0,0 +1,39 @@
+use quote::quote;
+
+/// Represents the case selected by the `rename_all` attribute of the `EnvStruct`.
+#[derive(Debug)]
+pub enum RenameAll {
+    /// `SCREAMING_SNAKE_CASE`, e.g. `DB_HOST`.
+    ScreamingSnake,
+    /// `preserve`, the field names as written.
+    Preserve,
+    /// `lowercase`, e.g. `db_host`.
+    Lower,
+    /// `kebab-case`, e.g. `db-host`.
+    Kebab,
+}
+
+impl RenameAll {
+    /// Generates a token stream for the matching `envstruct::NameCase`.
+    pub fn case_expr(&self) -> proc_macro2::TokenStream {
+        match self {
+            RenameAll::ScreamingSnake => quote!(::envstruct::NameCase::ScreamingSnake),
+            RenameAll::Preserve => quote!(::envstruct::NameCase::Preserve),
+            RenameAll::Lower => quote!(::envstruct::NameCase::Lower),
+            RenameAll::Kebab => quote!(::envstruct::NameCase::Kebab),
+        }
+    }
+}
+
+impl darling::FromMeta for RenameAll {
+    /// Parses the case from a string.
+    fn from_string(value: &str) -> darling::Result<Self> {
+        match value {
+            "SCREAMING_SNAKE_CASE" => Ok(RenameAll::ScreamingSnake),
+            "preserve" => Ok(RenameAll::Preserve),
+            "lowercase" | "snake_case" => Ok(RenameAll::Lower),
+            "kebab-case" => Ok(RenameAll::Kebab),
+            _ => Err(darling::Error::unknown_value(value)),
+        }
+    }
+}
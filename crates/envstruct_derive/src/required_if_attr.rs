@@ -0,0 +1,9 @@
+/// Represents the condition of the `required_if` relation, e.g.
+/// `required_if(field = "mode", value = "remote")`.
+#[derive(Debug, darling::FromMeta)]
+pub struct RequiredIfAttr {
+    /// The name of the field the condition is on.
+    pub field: String,
+    /// The value of the field requiring the variable.
+    pub value: String,
+}